@@ -1,4 +1,10 @@
 #[cfg(feature = "contract")]
+use crate::bytes::WriteError;
+use crate::crypto::PublicKey;
+use crate::lib::fmt;
+#[cfg(feature = "contract")]
+use crate::print::Print;
+#[cfg(feature = "contract")]
 use crate::time::Time;
 use crate::{eosio_name, n, NumBytes, Read, Write};
 
@@ -32,6 +38,34 @@ impl Authorization {
     }
 }
 
+impl From<Authorization> for Vec<Authorization> {
+    fn from(authorization: Authorization) -> Self {
+        vec![authorization]
+    }
+}
+
+/// A producer and the key it signs blocks with
+#[derive(Debug, PartialEq, Clone, Copy, Default, Read, Write, NumBytes)]
+pub struct ProducerKey {
+    pub producer_name: AccountName,
+    pub block_signing_key: PublicKey,
+}
+
+impl fmt::Display for Authorization {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}@{}", self.actor, self.permission)
+    }
+}
+
+#[cfg(feature = "contract")]
+impl Print for Authorization {
+    fn print(&self) {
+        self.actor.print();
+        '@'.print();
+        self.permission.print();
+    }
+}
+
 pub struct RamBytes(i64);
 pub struct NetWeight(i64);
 pub struct CpuWeight(i64);
@@ -80,28 +114,37 @@ impl AccountName {
         time.into()
     }
 
-    // TODO: support chains that have more/less than 21 producers
+    /// Returns the producers in the active schedule, in schedule order.
+    #[cfg(feature = "contract")]
+    pub fn active_producers() -> Vec<Self> {
+        let nullptr: *mut u64 = ::std::ptr::null_mut();
+        let num_bytes = unsafe { ::eosio_sys::get_active_producers(nullptr, 0) };
+        let mut producers = vec![0u64; num_bytes as usize / 8];
+        let producers_ptr = producers[..].as_mut_ptr();
+        let populated = unsafe { ::eosio_sys::get_active_producers(producers_ptr, num_bytes) };
+        producers.truncate(populated as usize / 8);
+        producers.into_iter().map(AccountName).collect()
+    }
+
+    /// Proposes a new producer schedule. The schedule becomes pending once the
+    /// block containing the proposal is irreversible, and active once the block
+    /// promoting it is irreversible. Returns the version of the proposed
+    /// schedule, or `None` if the proposal was rejected.
+    ///
+    /// Privileged. Note that EOSIO does not expose the proposed or pending
+    /// schedules to contracts, only the active one.
     #[cfg(feature = "contract")]
-    pub fn active_producers() -> [Option<Self>; 21] {
-        let mut producers = [0u64; 21];
-        let producers_ptr: *mut u64 = &mut producers as *mut _ as *mut u64;
-        let producers_len: u32 = 8 * 21;
-        unsafe { ::eosio_sys::get_active_producers(producers_ptr, producers_len) };
-
-        let mut options = [None; 21];
-        for (index, item) in options.iter_mut().enumerate() {
-            *item = match producers.get(index) {
-                Some(&producer) => {
-                    if producer == 0 {
-                        None
-                    } else {
-                        Some(AccountName(producer))
-                    }
-                }
-                None => None,
-            }
+    pub fn set_proposed_producers(producers: &[ProducerKey]) -> Result<Option<u64>, WriteError> {
+        let size = producers.num_bytes();
+        let mut bytes = vec![0u8; size];
+        let pos = producers.write(&mut bytes, 0)?;
+        let ptr = bytes[..].as_mut_ptr();
+        let version = unsafe { ::eosio_sys::set_proposed_producers(ptr, pos as u32) };
+        if version < 0 {
+            Ok(None)
+        } else {
+            Ok(Some(version as u64))
         }
-        options
     }
 
     // Priviledged
@@ -1,7 +1,10 @@
 use crate::account::{AccountName, Authorization};
-use crate::bytes::NumBytes;
 #[cfg(feature = "contract")]
-use crate::bytes::{Read, ReadError, Write, WriteError};
+use crate::assert::Assert;
+use crate::bytes::{NumBytes, Read, ReadError, UnsignedInt, Write, WriteError};
+use crate::transaction::PackedData;
+#[cfg(feature = "contract")]
+use crate::transaction::Transaction;
 use eosio_macros::*;
 
 /// This method will abort execution of wasm without failing the contract. This is used to bypass all cleanup / destructors that would normally be called.
@@ -16,7 +19,7 @@ where
 
 eosio_name!(ActionName);
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Default)]
 #[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub struct Action<Data> {
     pub account: AccountName,
@@ -30,14 +33,35 @@ where
     Data: NumBytes,
 {
     fn num_bytes(&self) -> usize {
+        let data_size = self.data.num_bytes();
         self.account.num_bytes()
             + self.name.num_bytes()
             + self.authorization.num_bytes()
-            + self.data.num_bytes()
+            + data_size.num_bytes()
+            + data_size
+    }
+}
+
+impl<Data> Read for Action<Data>
+where
+    Data: Read,
+{
+    fn read(bytes: &[u8], pos: usize) -> Result<(Self, usize), ReadError> {
+        let (account, pos) = AccountName::read(bytes, pos)?;
+        let (name, pos) = ActionName::read(bytes, pos)?;
+        let (authorization, pos) = Vec::<Authorization>::read(bytes, pos)?;
+        let (data_bytes, pos) = Vec::<u8>::read(bytes, pos)?;
+        let (data, _) = Data::read(&data_bytes, 0)?;
+        let action = Action {
+            account,
+            name,
+            authorization,
+            data,
+        };
+        Ok((action, pos))
     }
 }
 
-#[cfg(feature = "contract")]
 impl<Data> Write for Action<Data>
 where
     Data: Write + NumBytes,
@@ -56,8 +80,131 @@ where
     }
 }
 
-#[derive(Clone, Debug)]
-pub struct ActionId(u128);
+impl<Data> Action<Data>
+where
+    Data: Write + NumBytes,
+{
+    /// Serializes the action data, e.g. to include the action in a
+    /// [`Transaction`](struct.Transaction.html)
+    pub fn to_packed(&self) -> Result<Action<PackedData>, WriteError> {
+        let mut data = vec![0u8; self.data.num_bytes()];
+        self.data.write(&mut data, 0)?;
+        Ok(Action {
+            account: self.account,
+            name: self.name,
+            authorization: self.authorization.clone(),
+            data: PackedData(data),
+        })
+    }
+}
+
+/// An action that runs without authorization and can't access the database,
+/// e.g. to verify a signature before the rest of the transaction runs.
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct ContextFreeAction<Data> {
+    pub account: AccountName,
+    pub name: ActionName,
+    pub data: Data,
+}
+
+impl<Data> NumBytes for ContextFreeAction<Data>
+where
+    Data: NumBytes,
+{
+    fn num_bytes(&self) -> usize {
+        let data_size = self.data.num_bytes();
+        self.account.num_bytes()
+            + self.name.num_bytes()
+            + UnsignedInt(0).num_bytes()
+            + data_size.num_bytes()
+            + data_size
+    }
+}
+
+impl<Data> Read for ContextFreeAction<Data>
+where
+    Data: Read,
+{
+    fn read(bytes: &[u8], pos: usize) -> Result<(Self, usize), ReadError> {
+        let (action, pos) = Action::<Data>::read(bytes, pos)?;
+        if !action.authorization.is_empty() {
+            return Err(ReadError::InvalidData);
+        }
+        let action = ContextFreeAction {
+            account: action.account,
+            name: action.name,
+            data: action.data,
+        };
+        Ok((action, pos))
+    }
+}
+
+impl<Data> Write for ContextFreeAction<Data>
+where
+    Data: Write + NumBytes,
+{
+    fn write(&self, bytes: &mut [u8], pos: usize) -> Result<usize, WriteError> {
+        let pos = self.account.write(bytes, pos)?;
+        let pos = self.name.write(bytes, pos)?;
+        let pos = UnsignedInt(0).write(bytes, pos)?;
+
+        let data_size = self.data.num_bytes();
+        let mut data_bytes = vec![0u8; data_size];
+        self.data.write(&mut data_bytes, 0)?;
+
+        let pos = (&data_bytes[..]).write(bytes, pos)?;
+        Ok(pos)
+    }
+}
+
+impl<Data> ContextFreeAction<Data>
+where
+    Data: Write + NumBytes,
+{
+    /// Serializes the action data, e.g. to include the action in a
+    /// [`Transaction`](struct.Transaction.html)
+    pub fn to_packed(&self) -> Result<ContextFreeAction<PackedData>, WriteError> {
+        let mut data = vec![0u8; self.data.num_bytes()];
+        self.data.write(&mut data, 0)?;
+        Ok(ContextFreeAction {
+            account: self.account,
+            name: self.name,
+            data: PackedData(data),
+        })
+    }
+}
+
+#[cfg(feature = "contract")]
+impl<Data> ContextFreeAction<Data>
+where
+    Data: Write + NumBytes,
+{
+    pub fn send_inline(&self) -> Result<(), WriteError> {
+        let size = self.num_bytes() + 1; // 1 extra byte is needed
+        let mut bytes = vec![0u8; size];
+        let pos = self.write(&mut bytes, 0)?;
+        let ptr = bytes[..].as_mut_ptr();
+        unsafe { ::eosio_sys::send_context_free_inline(ptr, pos) }
+        Ok(())
+    }
+
+    /// Sends the action inline, aborting if it can't be serialized
+    pub fn send(&self) {
+        self.send_inline().assert("failed to send inline action");
+    }
+}
+
+/// The ID a deferred transaction is sent with, which can be used to cancel or
+/// replace it
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ActionId(pub u128);
+
+impl From<u128> for ActionId {
+    fn from(id: u128) -> Self {
+        ActionId(id)
+    }
+}
 
 #[cfg(feature = "contract")]
 impl<Data> Action<Data>
@@ -69,36 +216,82 @@ where
         let mut bytes = vec![0u8; size];
         let pos = self.write(&mut bytes, 0)?;
         let ptr = bytes[..].as_mut_ptr();
-        if self.authorization.is_empty() {
-            unsafe { ::eosio_sys::send_context_free_inline(ptr, pos) }
-        } else {
-            unsafe { ::eosio_sys::send_inline(ptr, pos) }
-        }
+        unsafe { ::eosio_sys::send_inline(ptr, pos) }
         Ok(())
     }
 
+    /// Sends the action inline, aborting if it can't be serialized
+    pub fn send(&self) {
+        self.send_inline().assert("failed to send inline action");
+    }
+
+    /// Sends the action in a deferred transaction that runs after `delay_sec`
+    /// seconds. `payer` pays for the RAM used until the transaction runs.
     pub fn send_deferred<P>(
         &self,
-        _id: ActionId,
-        _payer: P,
-        _replace_existing: bool,
+        id: ActionId,
+        payer: P,
+        delay_sec: u32,
+        replace_existing: bool,
     ) -> Result<(), WriteError>
     where
-        P: Into<u64>,
+        P: Into<AccountName>,
     {
-        // TODO
-        Ok(())
+        let mut transaction = Transaction::deferred(delay_sec);
+        transaction.actions.push(self.to_packed()?);
+        transaction.send_deferred(id, payer, replace_existing)
     }
 
-    pub fn cancel_deferred(_id: ActionId) -> Result<(), ()> {
-        // TODO
-        Ok(())
+    /// Cancels a deferred transaction. Returns `false` if it wasn't found.
+    pub fn cancel_deferred(id: ActionId) -> bool {
+        Transaction::cancel_deferred(id)
+    }
+}
+
+/// Reads the data of the current action
+#[cfg(feature = "contract")]
+pub fn read_action_data<T>() -> Result<(T, usize), ReadError>
+where
+    T: Read,
+{
+    let num_bytes = unsafe { ::eosio_sys::action_data_size() };
+    let mut bytes = vec![0u8; num_bytes as usize];
+    let ptr: *mut ::eosio_sys::c_void = &mut bytes[..] as *mut _ as *mut ::eosio_sys::c_void;
+    unsafe {
+        ::eosio_sys::read_action_data(ptr, num_bytes);
     }
+
+    T::read(&bytes, 0)
+}
+
+/// Sets the value returned by the current action, which is included in the
+/// action receipt
+#[cfg(feature = "contract")]
+pub fn set_action_return_value<T>(value: &T) -> Result<(), WriteError>
+where
+    T: Write + NumBytes,
+{
+    let mut bytes = vec![0u8; value.num_bytes()];
+    let pos = value.write(&mut bytes, 0)?;
+    let ptr: *mut ::eosio_sys::c_void = &mut bytes[..] as *mut _ as *mut ::eosio_sys::c_void;
+    unsafe { ::eosio_sys::set_action_return_value(ptr, pos) }
+    Ok(())
 }
 
 pub trait ToAction: Sized {
     const NAME: u64;
 
+    /// The ABI type of the value returned by the action, if any
+    const RETURN_TYPE: Option<&'static str> = None;
+
+    /// Whether the action is marked `#[eosio_action(context_free)]`.
+    ///
+    /// The macro rejects direct calls to authorization and database APIs in
+    /// the body of such actions, but only as a best-effort lint on names:
+    /// calls made through helper functions or handles passed in aren't caught,
+    /// and `nodeos` rejects those at runtime instead.
+    const CONTEXT_FREE: bool = false;
+
     fn to_action(self, account: AccountName, authorization: Vec<Authorization>) -> Action<Self> {
         Action {
             account,
@@ -107,6 +300,22 @@ pub trait ToAction: Sized {
             data: self,
         }
     }
+
+    fn to_context_free_action(self, account: AccountName) -> ContextFreeAction<Self> {
+        ContextFreeAction {
+            account,
+            name: Self::NAME.into(),
+            data: self,
+        }
+    }
+}
+
+/// Builds actions to send to a contract account, like the client structs from
+/// `eosio_abi!(..., client = ...)` and `#[eosio_contract]`. Every
+/// `#[eosio_action]` function has a `{Name}Client` trait that adds its method
+/// to all clients, so other crates can build typed actions for a contract.
+pub trait Client {
+    fn account(&self) -> AccountName;
 }
 
 #[cfg(feature = "contract")]
@@ -114,14 +323,7 @@ pub trait ActionFn: ToAction + Read + Write + NumBytes + Clone {
     fn execute(self);
 
     fn read_data() -> Result<(Self, usize), ReadError> {
-        let num_bytes = unsafe { ::eosio_sys::action_data_size() };
-        let mut bytes = vec![0u8; num_bytes as usize];
-        let ptr: *mut ::eosio_sys::c_void = &mut bytes[..] as *mut _ as *mut ::eosio_sys::c_void;
-        unsafe {
-            ::eosio_sys::read_action_data(ptr, num_bytes);
-        }
-
-        Self::read(&bytes, 0)
+        read_action_data()
     }
 
     fn send_inline(self, authorization: Vec<Authorization>) -> Result<(), WriteError> {
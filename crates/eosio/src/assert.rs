@@ -57,3 +57,37 @@ impl Assert<bool> for bool {
         }
     }
 }
+
+/// An error with a stable code that can be reported with `eosio_assert_code`.
+/// Usually implemented with `#[derive(ContractError)]`.
+pub trait ContractError {
+    /// Every code and message, for the ABI's `error_messages`
+    const ERROR_MESSAGES: &'static [(u64, &'static str)];
+
+    fn code(&self) -> u64;
+
+    fn message(&self) -> &'static str;
+
+    /// Aborts the action with this error's code
+    fn abort(&self) -> ! {
+        eosio_assert_code(false, self.code());
+        unreachable!();
+    }
+}
+
+pub trait OrAbort<T> {
+    /// Returns the value or aborts the action with the error's code
+    fn or_abort(self) -> T;
+}
+
+impl<T, E> OrAbort<T> for Result<T, E>
+where
+    E: ContractError,
+{
+    fn or_abort(self) -> T {
+        match self {
+            Ok(t) => t,
+            Err(e) => e.abort(),
+        }
+    }
+}
@@ -1,6 +1,8 @@
 use crate::account::AccountName;
 use crate::assert::*;
 use crate::lib::*;
+#[cfg(feature = "contract")]
+use crate::print::Print;
 use crate::symbol::Symbol;
 use eosio_macros::*;
 
@@ -17,12 +19,12 @@ impl Asset {
     }
 }
 
-impl ToString for Asset {
-    fn to_string(&self) -> String {
+impl fmt::Display for Asset {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let precision = self.symbol.precision();
         let amount = (self.amount as f64) / 10f64.powf(precision as f64);
         let symbol_name = self.symbol.name().to_string();
-        let mut s = amount.to_string();
+        let mut s: String = amount.to_string();
         let mut decimals = if s.contains('.') {
             s.as_str()
                 .rsplit('.')
@@ -39,7 +41,30 @@ impl ToString for Asset {
         }
         s.push_str(" ");
         s.push_str(&symbol_name);
-        s
+        f.pad(&s)
+    }
+}
+
+#[cfg(feature = "contract")]
+impl Print for Asset {
+    fn print(&self) {
+        let precision = self.symbol.precision() as u32;
+        let p10 = 10i64.pow(precision);
+        let whole = self.amount / p10;
+        let fraction = (self.amount % p10).abs();
+        if self.amount < 0 && whole == 0 {
+            '-'.print();
+        }
+        whole.print();
+        if precision > 0 {
+            '.'.print();
+            for i in (0..precision).rev() {
+                let digit = (fraction / 10i64.pow(i)) % 10;
+                digit.print();
+            }
+        }
+        ' '.print();
+        self.symbol.name().print();
     }
 }
 
@@ -1,4 +1,4 @@
-use crate::lib::TryInto;
+use crate::lib::{cmp, TryInto};
 
 #[cfg(all(feature = "alloc", not(feature = "std")))]
 use lib::{String, ToString, Vec};
@@ -6,6 +6,11 @@ use lib::{String, ToString, Vec};
 #[derive(Debug, Clone, Copy)]
 pub enum ReadError {
     NotEnoughBytes,
+    /// The bytes don't represent a valid value
+    InvalidData,
+    /// A versioned row has a tag that isn't one of its versions, e.g.
+    /// because it was written before the table was versioned
+    UnknownVersion(u8),
 }
 
 pub trait Read: Sized {
@@ -95,7 +100,9 @@ impl_num!(
     i32, 4
     u64, 8
     i64, 8
-); // TODO i8 u128 i128
+    u128, 16
+    i128, 16
+); // TODO i8
 
 impl Read for f32 {
     fn read(bytes: &[u8], pos: usize) -> Result<(Self, usize), ReadError> {
@@ -156,23 +163,158 @@ impl NumBytes for bool {
     }
 }
 
+// Lengths are written as `varuint32`
 impl Read for usize {
     fn read(bytes: &[u8], offset: usize) -> Result<(Self, usize), ReadError> {
-        // TODO: fix this. usize isn't always u8?
-        u8::read(bytes, offset).map(|(v, c)| (v as usize, c))
+        UnsignedInt::read(bytes, offset).map(|(v, c)| (v.0 as usize, c))
     }
 }
 
 impl Write for usize {
     fn write(&self, bytes: &mut [u8], pos: usize) -> Result<usize, WriteError> {
-        // TODO: fix this when usize is larger than 1 byte?
-        (*self as u8).write(bytes, pos)
+        UnsignedInt(*self as u32).write(bytes, pos)
     }
 }
 
 impl NumBytes for usize {
     fn num_bytes(&self) -> usize {
-        1
+        UnsignedInt(*self as u32).num_bytes()
+    }
+}
+
+/// A variable-length unsigned integer, `varuint32` in ABIs
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct UnsignedInt(pub u32);
+
+impl From<u32> for UnsignedInt {
+    fn from(value: u32) -> Self {
+        UnsignedInt(value)
+    }
+}
+
+impl From<UnsignedInt> for u32 {
+    fn from(value: UnsignedInt) -> Self {
+        value.0
+    }
+}
+
+impl Read for UnsignedInt {
+    fn read(bytes: &[u8], pos: usize) -> Result<(Self, usize), ReadError> {
+        let mut value = 0u32;
+        let mut pos = pos;
+        // A u32 takes at most 5 bytes
+        for i in 0..5 {
+            let (byte, p) = u8::read(bytes, pos)?;
+            pos = p;
+            value |= u32::from(byte & 0x7f) << (7 * i);
+            if byte & 0x80 == 0 {
+                break;
+            }
+        }
+        Ok((UnsignedInt(value), pos))
+    }
+}
+
+impl Write for UnsignedInt {
+    fn write(&self, bytes: &mut [u8], pos: usize) -> Result<usize, WriteError> {
+        let mut value = self.0;
+        let mut pos = pos;
+        loop {
+            let mut byte = (value & 0x7f) as u8;
+            value >>= 7;
+            if value > 0 {
+                byte |= 0x80;
+            }
+            pos = byte.write(bytes, pos)?;
+            if value == 0 {
+                return Ok(pos);
+            }
+        }
+    }
+}
+
+impl NumBytes for UnsignedInt {
+    fn num_bytes(&self) -> usize {
+        let mut count = 1;
+        let mut value = self.0 >> 7;
+        while value > 0 {
+            count += 1;
+            value >>= 7;
+        }
+        count
+    }
+}
+
+/// The bits of a 128-bit float, `float128` in ABIs and the key type of
+/// `idx_long_double` secondary indexes. Rust has no 128-bit float type, so
+/// values can only be created from `f64`/`f32`, which is exact.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, Default)]
+#[repr(transparent)]
+pub struct Float128(pub u128);
+
+impl From<f64> for Float128 {
+    fn from(value: f64) -> Self {
+        let bits = value.to_bits();
+        let sign = u128::from(bits >> 63) << 127;
+        let exponent = (bits >> 52) & 0x7ff;
+        let fraction = bits & 0x000f_ffff_ffff_ffff;
+        let (exponent, fraction) = match exponent {
+            0 if fraction == 0 => (0, 0),
+            // Subnormal values are normal in 128 bits
+            0 => {
+                let msb = 63 - u64::from(fraction.leading_zeros());
+                let fraction = u128::from(fraction ^ (1 << msb)) << (112 - msb);
+                (msb + 16383 - 1074, fraction)
+            }
+            // Infinity and NaN
+            0x7ff => (0x7fff, u128::from(fraction) << 60),
+            _ => (exponent + 16383 - 1023, u128::from(fraction) << 60),
+        };
+        Float128(sign | (u128::from(exponent) << 112) | fraction)
+    }
+}
+
+/// Orders by value, except that `-0` sorts before `0` and NaNs sort at the
+/// ends, so the order agrees with `Eq`
+impl Ord for Float128 {
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        // Flip the magnitude of negative values so the bits sort as integers
+        let key = |value: &Float128| {
+            let bits = value.0 as i128;
+            bits ^ (((bits >> 127) as u128) >> 1) as i128
+        };
+        key(self).cmp(&key(other))
+    }
+}
+
+impl PartialOrd for Float128 {
+    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl From<f32> for Float128 {
+    fn from(value: f32) -> Self {
+        Float128::from(f64::from(value))
+    }
+}
+
+impl Read for Float128 {
+    fn read(bytes: &[u8], pos: usize) -> Result<(Self, usize), ReadError> {
+        u128::read(bytes, pos).map(|(bits, pos)| (Float128(bits), pos))
+    }
+}
+
+impl Write for Float128 {
+    fn write(&self, bytes: &mut [u8], pos: usize) -> Result<usize, WriteError> {
+        self.0.write(bytes, pos)
+    }
+}
+
+impl NumBytes for Float128 {
+    fn num_bytes(&self) -> usize {
+        self.0.num_bytes()
     }
 }
 
@@ -215,6 +357,19 @@ where
     }
 }
 
+impl<'a, T> NumBytes for &'a [T]
+where
+    T: NumBytes,
+{
+    fn num_bytes(&self) -> usize {
+        let mut count = self.len().num_bytes();
+        for item in self.iter() {
+            count += item.num_bytes();
+        }
+        count
+    }
+}
+
 #[cfg(any(feature = "std", feature = "alloc"))]
 impl<T> Read for Vec<T>
 where
@@ -243,7 +398,7 @@ where
     T: NumBytes,
 {
     fn num_bytes(&self) -> usize {
-        let mut count = 1;
+        let mut count = self.len().num_bytes();
         for item in self.iter() {
             count += item.num_bytes();
         }
@@ -300,7 +455,7 @@ macro_rules! impl_array {
             T: NumBytes,
         {
             fn num_bytes(&self) -> usize {
-                let mut count = 1;
+                let mut count = self.len().num_bytes();
                 for item in self.iter() {
                     count += item.num_bytes();
                 }
@@ -338,7 +493,7 @@ impl Write for String {
 #[cfg(any(feature = "std", feature = "alloc"))]
 impl NumBytes for String {
     fn num_bytes(&self) -> usize {
-        1 + self.len() // TODO: utf16?
+        self.len().num_bytes() + self.len() // TODO: utf16?
     }
 }
 
@@ -350,7 +505,7 @@ impl<'a> Write for &'a str {
 
 impl<'a> NumBytes for &'a str {
     fn num_bytes(&self) -> usize {
-        1 + self.len() // TODO: utf16?
+        self.len().num_bytes() + self.len() // TODO: utf16?
     }
 }
 
@@ -0,0 +1,19 @@
+use crate::account::AccountName;
+
+/// A contract whose actions are methods on `Self`.
+///
+/// Add `#[eosio_contract]` to the contract struct and to an `impl` block. Every
+/// `pub fn` in that block becomes an action, and an `apply` function is
+/// generated which constructs the contract with `Contract::new` and calls the
+/// matching method.
+///
+/// On the struct, `#[eosio_contract]` adds `receiver` and `code` fields and
+/// implements this trait. Other fields are set with `#[init(expr)]`, where
+/// `expr` can use those two names, e.g.
+/// `#[init(Address::table(receiver, receiver))]`, or with `Default::default()`.
+///
+/// `receiver` is the account whose code is executing. `code` is the account the
+/// action was sent to, which the CDT also calls the first receiver.
+pub trait Contract: Sized {
+    fn new(receiver: AccountName, code: AccountName) -> Self;
+}
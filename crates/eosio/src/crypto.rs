@@ -1,14 +1,42 @@
 #[cfg(feature = "contract")]
 use crate::assert::Assert;
+use crate::bytes::{NumBytes, Read, ReadError, Write, WriteError};
+use crate::lib::fmt;
+#[cfg(feature = "contract")]
+use crate::print::Print;
 use eosio_macros::*;
 #[cfg(feature = "contract")]
-use eosio_sys::{capi_checksum160, capi_checksum256, capi_checksum512};
+use eosio_sys::{c_void, capi_checksum160, capi_checksum256, capi_checksum512};
 
 #[cfg(feature = "contract")]
 pub trait Hasher: Assert<()> {
     fn new(data: &str) -> Self;
 }
 
+/// `Print` and `Display` as lowercase hex, like `printhex`
+macro_rules! impl_hex {
+    ($($t:ty)*) => ($(
+        #[cfg(feature = "contract")]
+        impl Print for $t {
+            fn print(&self) {
+                let ptr: *const c_void = &self.0[..] as *const _ as *const c_void;
+                unsafe { ::eosio_sys::printhex(ptr, self.0.len() as u32) }
+            }
+        }
+
+        impl fmt::Display for $t {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                for byte in self.0.iter() {
+                    write!(f, "{:02x}", byte)?;
+                }
+                Ok(())
+            }
+        }
+    )*)
+}
+
+impl_hex!(Ripemd160 Sha1 Sha256 Sha512 PublicKey);
+
 #[derive(
     Read, Write, NumBytes, Default, Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord,
 )]
@@ -71,9 +99,23 @@ impl Assert<()> for Sha1 {
     }
 }
 
-#[derive(Read, Write, NumBytes, Default, Clone, Copy)]
+#[derive(
+    Read, Write, NumBytes, Default, Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord,
+)]
 pub struct Sha256([u8; 32usize]);
 
+impl Sha256 {
+    pub fn as_bytes(&self) -> &[u8; 32usize] {
+        &self.0
+    }
+}
+
+impl From<[u8; 32usize]> for Sha256 {
+    fn from(bytes: [u8; 32usize]) -> Self {
+        Sha256(bytes)
+    }
+}
+
 #[cfg(feature = "contract")]
 impl Hasher for Sha256 {
     fn new(data: &str) -> Self {
@@ -122,3 +164,60 @@ impl Assert<()> for Sha512 {
         unsafe { ::eosio_sys::assert_sha512(data_ptr, data_len, c_hash_ptr) }
     }
 }
+
+/// EOSIO public key. The first byte is the key type, followed by 33 bytes of key data.
+#[derive(Clone, Copy)]
+pub struct PublicKey([u8; 34usize]);
+
+impl Default for PublicKey {
+    fn default() -> Self {
+        PublicKey([0u8; 34usize])
+    }
+}
+
+impl PartialEq for PublicKey {
+    fn eq(&self, other: &PublicKey) -> bool {
+        self.0[..] == other.0[..]
+    }
+}
+
+impl ::std::fmt::Debug for PublicKey {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "PublicKey({:?})", &self.0[..])
+    }
+}
+
+impl From<[u8; 34usize]> for PublicKey {
+    fn from(data: [u8; 34usize]) -> Self {
+        PublicKey(data)
+    }
+}
+
+impl Read for PublicKey {
+    fn read(bytes: &[u8], pos: usize) -> Result<(Self, usize), ReadError> {
+        let mut data = [0u8; 34usize];
+        let mut pos = pos;
+        for byte in data.iter_mut() {
+            let (b, p) = u8::read(bytes, pos)?;
+            *byte = b;
+            pos = p;
+        }
+        Ok((PublicKey(data), pos))
+    }
+}
+
+impl Write for PublicKey {
+    fn write(&self, bytes: &mut [u8], pos: usize) -> Result<usize, WriteError> {
+        let mut pos = pos;
+        for byte in self.0.iter() {
+            pos = byte.write(bytes, pos)?;
+        }
+        Ok(pos)
+    }
+}
+
+impl NumBytes for PublicKey {
+    fn num_bytes(&self) -> usize {
+        34
+    }
+}
@@ -15,7 +15,13 @@ mod lib {
         pub use std::*;
     }
 
+    #[cfg(feature = "contract")]
+    pub use self::core::cell::{Cell, RefCell};
+    pub use self::core::cmp;
     pub use self::core::convert::{TryFrom, TryInto};
+    pub use self::core::fmt;
+    #[cfg(feature = "contract")]
+    pub use self::core::iter;
     pub use self::core::marker::PhantomData;
     pub use self::core::ops::*;
     pub use self::core::str::FromStr;
@@ -29,7 +35,6 @@ mod lib {
     pub use alloc::vec::Vec;
     #[cfg(feature = "std")]
     pub use std::vec::Vec;
-
 }
 
 mod account;
@@ -37,17 +42,23 @@ mod action;
 mod assert;
 mod asset;
 mod bytes;
+mod contract;
 mod crypto;
 #[cfg(feature = "serde")]
 pub mod json;
 mod print;
+#[cfg(feature = "contract")]
+pub mod runtime;
 mod symbol;
 mod table;
 #[cfg(feature = "contract")]
 mod table_primary;
 #[cfg(feature = "contract")]
 mod table_secondary;
+#[cfg(feature = "contract")]
+mod table_singleton;
 mod time;
+mod transaction;
 
 pub mod sys {
     pub use eosio_sys::*;
@@ -58,6 +69,7 @@ pub use self::action::*;
 pub use self::assert::*;
 pub use self::asset::*;
 pub use self::bytes::*;
+pub use self::contract::*;
 pub use self::crypto::*;
 pub use self::print::*;
 pub use self::symbol::*;
@@ -66,7 +78,10 @@ pub use self::table::*;
 pub use self::table_primary::*;
 #[cfg(feature = "contract")]
 pub use self::table_secondary::*;
+#[cfg(feature = "contract")]
+pub use self::table_singleton::*;
 pub use self::time::*;
+pub use self::transaction::*;
 pub use eosio_macros::*;
 pub use eosio_sys::{ParseNameError, ParseSymbolError};
 
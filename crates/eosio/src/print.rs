@@ -1,7 +1,40 @@
+use crate::lib::fmt;
+#[cfg(any(feature = "std", feature = "alloc"))]
+use crate::lib::String;
+
 pub trait Print {
     fn print(&self);
 }
 
+/// Console output as a `core::fmt::Write` sink, backed by `prints_l`.
+#[cfg(feature = "contract")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Console;
+
+#[cfg(feature = "contract")]
+impl fmt::Write for Console {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        s.print();
+        Ok(())
+    }
+}
+
+/// Prints formatted arguments to the console. Used by `eosio_println!`.
+#[cfg(feature = "contract")]
+pub fn print_args(args: fmt::Arguments) {
+    // writing to the console never fails
+    let _ = fmt::Write::write_fmt(&mut Console, args);
+}
+
+/// Formats arguments into a `String`. Used by `eosio_format!`.
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub fn format_to_string(args: fmt::Arguments) -> String {
+    let mut s = String::new();
+    // writing to a string never fails
+    let _ = fmt::Write::write_fmt(&mut s, args);
+    s
+}
+
 #[cfg(feature = "contract")]
 impl Print for u8 {
     fn print(&self) {
@@ -129,3 +162,107 @@ where
         }
     }
 }
+
+#[cfg(feature = "contract")]
+impl<P, E> Print for Result<P, E>
+where
+    P: Print,
+    E: Print,
+{
+    fn print(&self) {
+        match self {
+            Ok(p) => {
+                "Ok(".print();
+                p.print();
+                ")".print();
+            }
+            Err(e) => {
+                "Err(".print();
+                e.print();
+                ")".print();
+            }
+        }
+    }
+}
+
+#[cfg(feature = "contract")]
+impl<'a, P> Print for &'a [P]
+where
+    P: Print,
+{
+    fn print(&self) {
+        "[".print();
+        for (i, item) in self.iter().enumerate() {
+            if i > 0 {
+                ", ".print();
+            }
+            item.print();
+        }
+        "]".print();
+    }
+}
+
+#[cfg(feature = "contract")]
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl<P> Print for crate::lib::Vec<P>
+where
+    P: Print,
+{
+    fn print(&self) {
+        (&self[..]).print()
+    }
+}
+
+#[cfg(feature = "contract")]
+impl<A, B> Print for (A, B)
+where
+    A: Print,
+    B: Print,
+{
+    fn print(&self) {
+        "(".print();
+        self.0.print();
+        ", ".print();
+        self.1.print();
+        ")".print();
+    }
+}
+
+#[cfg(feature = "contract")]
+impl<A, B, C> Print for (A, B, C)
+where
+    A: Print,
+    B: Print,
+    C: Print,
+{
+    fn print(&self) {
+        "(".print();
+        self.0.print();
+        ", ".print();
+        self.1.print();
+        ", ".print();
+        self.2.print();
+        ")".print();
+    }
+}
+
+#[cfg(feature = "contract")]
+impl<A, B, C, D> Print for (A, B, C, D)
+where
+    A: Print,
+    B: Print,
+    C: Print,
+    D: Print,
+{
+    fn print(&self) {
+        "(".print();
+        self.0.print();
+        ", ".print();
+        self.1.print();
+        ", ".print();
+        self.2.print();
+        ", ".print();
+        self.3.print();
+        ")".print();
+    }
+}
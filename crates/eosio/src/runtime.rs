@@ -0,0 +1,145 @@
+//! Runtime support for contracts: reporting panics through `eosio_assert` and
+//! a bump allocator.
+//!
+//! Everything here is opt-in through features:
+//!
+//! - `panic-hook` makes [`init`] install a panic hook, so panic messages and
+//!   locations show up in the failed transaction instead of an unexplained
+//!   abort. This enables `std`, which installing the hook needs.
+//! - `strip-panic-strings` makes the hook report a fixed message instead of
+//!   formatting the panic, for production builds that shouldn't carry the
+//!   panic formatting code. It enables `panic-hook`.
+//! - `bump-alloc` uses [`BumpAllocator`] as the global allocator.
+
+#[cfg(all(feature = "panic-hook", not(feature = "strip-panic-strings")))]
+use crate::lib::fmt;
+
+/// Called at the start of every generated `apply` function
+pub fn init() {
+    #[cfg(feature = "panic-hook")]
+    ::std::panic::set_hook(Box::new(|info| {
+        on_panic(info);
+    }));
+}
+
+/// Aborts the action with a message describing the panic
+#[cfg(all(feature = "panic-hook", not(feature = "strip-panic-strings")))]
+fn on_panic<P: fmt::Display>(info: &P) -> ! {
+    let mut message = PanicMessage::new();
+    let _ = fmt::Write::write_fmt(&mut message, format_args!("{}", info));
+    unsafe {
+        ::eosio_sys::eosio_assert_message(0, message.bytes.as_ptr(), message.len as u32);
+    }
+    unreachable!()
+}
+
+/// Aborts the action without formatting the panic
+#[cfg(feature = "strip-panic-strings")]
+fn on_panic<P>(_info: &P) -> ! {
+    let message = "panicked";
+    unsafe {
+        ::eosio_sys::eosio_assert_message(0, message.as_ptr(), message.len() as u32);
+    }
+    unreachable!()
+}
+
+/// A fixed-size buffer for panic messages, so panicking doesn't allocate.
+/// Longer messages are truncated.
+#[cfg(all(feature = "panic-hook", not(feature = "strip-panic-strings")))]
+struct PanicMessage {
+    bytes: [u8; 256],
+    len: usize,
+}
+
+#[cfg(all(feature = "panic-hook", not(feature = "strip-panic-strings")))]
+impl PanicMessage {
+    fn new() -> Self {
+        PanicMessage {
+            bytes: [0u8; 256],
+            len: 0,
+        }
+    }
+}
+
+#[cfg(all(feature = "panic-hook", not(feature = "strip-panic-strings")))]
+impl fmt::Write for PanicMessage {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let end = (self.len + s.len()).min(self.bytes.len());
+        let count = end - self.len;
+        self.bytes[self.len..end].copy_from_slice(&s.as_bytes()[..count]);
+        self.len = end;
+        Ok(())
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+pub use self::bump::BumpAllocator;
+
+#[cfg(target_arch = "wasm32")]
+mod bump {
+    use core::alloc::{GlobalAlloc, Layout};
+    use core::arch::wasm32;
+    use core::cell::Cell;
+    use core::ptr;
+
+    const PAGE_SIZE: usize = 64 * 1024;
+
+    /// An allocator that never frees memory. Every action runs in a fresh
+    /// wasm instance, so this is usually all a contract needs and it's much
+    /// smaller than the default allocator.
+    ///
+    /// ```ignore
+    /// #[global_allocator]
+    /// static ALLOC: eosio::runtime::BumpAllocator = eosio::runtime::BumpAllocator::new();
+    /// ```
+    pub struct BumpAllocator {
+        next: Cell<usize>,
+        end: Cell<usize>,
+    }
+
+    // Contracts are single-threaded
+    unsafe impl Sync for BumpAllocator {}
+
+    impl BumpAllocator {
+        pub const fn new() -> Self {
+            BumpAllocator {
+                next: Cell::new(0),
+                end: Cell::new(0),
+            }
+        }
+    }
+
+    unsafe impl GlobalAlloc for BumpAllocator {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            if self.end.get() == 0 {
+                let end = wasm32::memory_size(0) * PAGE_SIZE;
+                self.next.set(end);
+                self.end.set(end);
+            }
+
+            let align = layout.align();
+            let start = (self.next.get() + align - 1) & !(align - 1);
+            let next = match start.checked_add(layout.size()) {
+                Some(next) => next,
+                None => return ptr::null_mut(),
+            };
+
+            if next > self.end.get() {
+                let pages = (next - self.end.get() + PAGE_SIZE - 1) / PAGE_SIZE;
+                if wasm32::memory_grow(0, pages) == usize::max_value() {
+                    return ptr::null_mut();
+                }
+                self.end.set(self.end.get() + pages * PAGE_SIZE);
+            }
+
+            self.next.set(next);
+            start as *mut u8
+        }
+
+        unsafe fn dealloc(&self, _ptr: *mut u8, _layout: Layout) {}
+    }
+
+    #[cfg(feature = "bump-alloc")]
+    #[global_allocator]
+    static ALLOC: BumpAllocator = BumpAllocator::new();
+}
@@ -1,4 +1,6 @@
 use crate::account::AccountName;
+use crate::lib::fmt;
+#[cfg(feature = "contract")]
 use crate::print::Print;
 use eosio_macros::*;
 
@@ -25,11 +27,11 @@ impl From<SymbolName> for [char; 7] {
     }
 }
 
-impl ToString for SymbolName {
-    fn to_string(&self) -> String {
+impl fmt::Display for SymbolName {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let chars: [char; 7] = (*self).into();
         let s: String = chars.iter().collect();
-        s.trim().to_string()
+        f.pad(s.trim())
     }
 }
 
@@ -103,6 +105,12 @@ impl From<u64> for Symbol {
     }
 }
 
+impl fmt::Display for Symbol {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{},{}", self.precision(), self.name())
+    }
+}
+
 #[cfg(feature = "contract")]
 impl Print for Symbol {
     fn print(&self) {
@@ -124,6 +132,12 @@ pub struct ExtendedSymbol {
     pub contract: AccountName,
 }
 
+impl fmt::Display for ExtendedSymbol {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}@{}", self.symbol, self.contract)
+    }
+}
+
 #[cfg(feature = "contract")]
 impl Print for ExtendedSymbol {
     fn print(&self) {
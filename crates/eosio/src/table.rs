@@ -1,9 +1,17 @@
 #[cfg(feature = "contract")]
 use crate::account::AccountName;
-use crate::bytes::NumBytes;
+#[cfg(feature = "contract")]
+use crate::assert::Assert;
+use crate::bytes::{Float128, NumBytes};
 #[cfg(feature = "contract")]
 use crate::bytes::{Read, ReadError, Write, WriteError};
+use crate::crypto::Sha256;
+#[cfg(feature = "contract")]
+use crate::lib::iter::Rev;
+#[cfg(feature = "contract")]
+use crate::lib::{Bound, PhantomData};
 use crate::symbol::SymbolName;
+use crate::time::Time;
 use eosio_macros::*;
 
 eosio_name!(TableName);
@@ -26,30 +34,174 @@ impl From<ScopeName> for SymbolName {
 
 #[cfg(not(feature = "contract"))]
 pub trait TableRow: NumBytes {
-    const TABLE_NAME: u64;
+    const TABLE_NAME: TableName;
+
+    /// The kind of name the table is scoped by, e.g. `AccountName`
+    type Scope: Into<ScopeName>;
 
     fn primary_key(&self) -> u64;
+
+    fn secondary_keys(&self) -> [Option<SecondaryKey>; 16] {
+        [None; 16]
+    }
 }
 
 #[cfg(feature = "contract")]
 pub trait TableRow: Read + Write + NumBytes {
-    const TABLE_NAME: u64;
+    const TABLE_NAME: TableName;
+
+    /// The kind of name the table is scoped by, e.g. `AccountName`
+    type Scope: Into<ScopeName>;
 
     fn primary_key(&self) -> u64;
 
-    fn secondary_keys(&self) -> [Option<&crate::table_secondary::SecondaryTableKey>; 16] {
+    fn secondary_keys(&self) -> [Option<SecondaryKey>; 16] {
         [None; 16]
     }
 
     fn table<C, S>(code: C, scope: S) -> crate::table_primary::PrimaryTableIndex<Self>
     where
         C: Into<AccountName>,
-        S: Into<ScopeName>,
+        S: Into<Self::Scope>,
     {
+        let scope: Self::Scope = scope.into();
         crate::table_primary::PrimaryTableIndex::new(code, scope, Self::TABLE_NAME)
     }
 }
 
+/// RAM estimates for any table row
+pub trait RowRam: TableRow {
+    /// The RAM billed for storing this row, including its secondary keys
+    fn billable_size(&self) -> usize {
+        let keys: usize = self
+            .secondary_keys()
+            .iter()
+            .filter_map(|key| key.map(|key| key.billable_size()))
+            .sum();
+        ROW_OVERHEAD + self.num_bytes() + keys
+    }
+
+    /// The change in RAM billed when `old` is replaced by `new`, where `None`
+    /// means there is no row. Assumes both are paid for by the same account,
+    /// and leaves out [`TABLE_OVERHEAD`](constant.TABLE_OVERHEAD.html).
+    fn estimate_ram_delta(old: Option<&Self>, new: Option<&Self>) -> i64 {
+        let old = old.map_or(0, Self::billable_size) as i64;
+        let new = new.map_or(0, Self::billable_size) as i64;
+        new - old
+    }
+}
+
+impl<T> RowRam for T where T: TableRow {}
+
+/// A row stored after a version tag, from `#[eosio_table(name, version = N)]`.
+/// Rows of an older version `V` are read with the row type's
+/// `fn migrate_from_vV(old: OldRow) -> Self`, and upgraded when written.
+///
+/// Rows written before the table was versioned have no tag, and reading one
+/// would take its first byte as the version. Version a table under a new
+/// name instead, and move the untagged rows over with
+/// `PrimaryTableIndex::migrate_untagged`.
+pub trait VersionedRow: TableRow {
+    const VERSION: u8;
+}
+
+#[cfg(not(feature = "contract"))]
+pub trait SingletonRow: NumBytes {
+    const TABLE_NAME: TableName;
+}
+
+/// A row stored in a [`Singleton`](struct.Singleton.html)
+#[cfg(feature = "contract")]
+pub trait SingletonRow: Read + Write + NumBytes {
+    const TABLE_NAME: TableName;
+
+    fn singleton<C, S>(code: C, scope: S) -> crate::table_singleton::Singleton<Self>
+    where
+        C: Into<AccountName>,
+        S: Into<ScopeName>,
+    {
+        crate::table_singleton::Singleton::new(code, scope, Self::TABLE_NAME)
+    }
+}
+
+/// RAM billed for each row on top of its data, from `key_value_object`
+pub const ROW_OVERHEAD: usize = 108;
+
+/// RAM billed once per table and scope, when its first row is stored
+pub const TABLE_OVERHEAD: usize = 108;
+
+/// A secondary key of any index type, as returned by
+/// [`TableRow::secondary_keys`](trait.TableRow.html#method.secondary_keys)
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub enum SecondaryKey {
+    U64(u64),
+    F64(f64),
+    U128(u128),
+    H256(Sha256),
+    F128(Float128),
+}
+
+impl SecondaryKey {
+    /// The RAM billed for storing this key in its index, including overhead
+    pub fn billable_size(&self) -> usize {
+        match self {
+            SecondaryKey::U64(_) | SecondaryKey::F64(_) => 128,
+            SecondaryKey::U128(_) | SecondaryKey::F128(_) => 136,
+            SecondaryKey::H256(_) => 152,
+        }
+    }
+}
+
+macro_rules! secondary_key_from {
+    ($($t:ty, $variant:ident)*) => ($(
+        impl From<$t> for SecondaryKey {
+            fn from(key: $t) -> Self {
+                SecondaryKey::$variant(key)
+            }
+        }
+    )*)
+}
+
+secondary_key_from!(
+    u64, U64
+    f64, F64
+    u128, U128
+    Sha256, H256
+    Float128, F128
+);
+
+macro_rules! secondary_key_converted_from {
+    ($($to:ty, $from:ty => $convert:expr;)*) => ($(
+        impl From<$from> for SecondaryKey {
+            fn from(key: $from) -> Self {
+                SecondaryKey::from(<$to>::from(($convert)(key)))
+            }
+        }
+    )*)
+}
+
+// Signed integers are stored as their two's complement, like in the C++ SDK,
+// so negative keys sort after positive ones.
+secondary_key_converted_from!(
+    u64, u8 => u64::from;
+    u64, u16 => u64::from;
+    u64, u32 => u64::from;
+    u64, i64 => |key: i64| key as u64;
+    u64, SymbolName => u64::from;
+    u64, Time => u64::from;
+    f64, f32 => f64::from;
+);
+
+/// Packs two 64-bit keys into an `idx128` key that sorts by `high`, then by
+/// `low`, e.g. for a composite secondary key
+pub fn pack_u128<H, L>(high: H, low: L) -> u128
+where
+    H: Into<u64>,
+    L: Into<u64>,
+{
+    (u128::from(high.into()) << 64) | u128::from(low.into())
+}
+
 /// Table Cursor
 #[cfg(feature = "contract")]
 pub trait TableCursor<T>: IntoIterator
@@ -58,7 +210,14 @@ where
 {
     fn get(&self) -> Result<T, ReadError>;
     fn erase(&self) -> Result<T, ReadError>;
+    /// Replaces the row. Prefer `update`, which can't change the primary key.
     fn modify(&self, payer: Option<AccountName>, item: &T) -> Result<usize, WriteError>;
+    /// Reads the row, changes it with `f` and writes it back, returning the
+    /// new row. Only secondary keys that changed are written, unless `payer`
+    /// is given. Aborts the action if `f` changes the primary key.
+    fn update<F>(&self, payer: Option<AccountName>, f: F) -> Result<T, WriteError>
+    where
+        F: FnOnce(&mut T);
 }
 
 /// Table index
@@ -79,4 +238,75 @@ where
 
 /// Table iterator
 #[cfg(feature = "contract")]
-pub trait TableIterator: DoubleEndedIterator {}
+pub trait TableIterator: DoubleEndedIterator {
+    /// Reads the row behind each cursor, e.g. for
+    /// `index.range(..).rows().take_while(...)`. Aborts the action if a row
+    /// can't be read.
+    fn rows<T>(self) -> TableRows<Self, T>
+    where
+        Self: Sized,
+        Self::Item: TableCursor<T>,
+        T: TableRow,
+    {
+        TableRows {
+            iter: self,
+            _data: PhantomData,
+        }
+    }
+}
+
+#[cfg(feature = "contract")]
+impl<I> TableIterator for Rev<I> where I: TableIterator {}
+
+/// An iterator over rows instead of cursors, see
+/// [`TableIterator::rows`](trait.TableIterator.html#method.rows)
+#[cfg(feature = "contract")]
+#[derive(Clone, Debug)]
+pub struct TableRows<I, T> {
+    iter: I,
+    _data: PhantomData<T>,
+}
+
+#[cfg(feature = "contract")]
+impl<I, T> Iterator for TableRows<I, T>
+where
+    I: TableIterator,
+    I::Item: TableCursor<T>,
+    T: TableRow,
+{
+    type Item = T;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter
+            .next()
+            .map(|cursor| cursor.get().assert("failed to read table row"))
+    }
+}
+
+#[cfg(feature = "contract")]
+impl<I, T> DoubleEndedIterator for TableRows<I, T>
+where
+    I: TableIterator,
+    I::Item: TableCursor<T>,
+    T: TableRow,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter
+            .next_back()
+            .map(|cursor| cursor.get().assert("failed to read table row"))
+    }
+}
+
+/// Whether a range of keys is empty without looking at the table, e.g. `5..3`
+#[cfg(feature = "contract")]
+pub(crate) fn range_is_empty<K>(start: Bound<K>, end: Bound<K>) -> bool
+where
+    K: PartialOrd,
+{
+    match (start, end) {
+        (Bound::Included(start), Bound::Included(end)) => start > end,
+        (Bound::Included(start), Bound::Excluded(end))
+        | (Bound::Excluded(start), Bound::Included(end))
+        | (Bound::Excluded(start), Bound::Excluded(end)) => start >= end,
+        _ => false,
+    }
+}
@@ -1,36 +1,51 @@
 use crate::account::AccountName;
-use crate::bytes::{ReadError, WriteError};
-use crate::lib::PhantomData;
+use crate::assert::{eosio_assert, Assert};
+use crate::bytes::{Read, ReadError, WriteError};
+use crate::lib::iter::Rev;
+use crate::lib::{fmt, Bound, Cell, PhantomData, RangeBounds, RefCell};
 use crate::print::Print;
 use crate::table::*;
+use crate::table_secondary::{SecondaryTableKey, SecondaryTableName};
 use eosio_sys::ctypes::*;
+use std::collections::BTreeMap;
+use std::rc::Rc;
 
 #[derive(Copy, Clone, Debug)]
-pub struct PrimaryTableCursor<T>
+pub struct PrimaryTableCursor<'a, T>
 where
     T: TableRow,
 {
     value: i32,
-    code: AccountName,
-    scope: ScopeName,
-    table: TableName,
-    _data: PhantomData<T>,
+    index: &'a PrimaryTableIndex<T>,
+    /// Whether `get` adds the row to the cache. Rows read while iterating
+    /// aren't added, so scanning a table doesn't evict the rows an action
+    /// keeps coming back to.
+    cache_reads: bool,
 }
 
-impl<T> PartialEq for PrimaryTableCursor<T>
+impl<'a, T> PartialEq for PrimaryTableCursor<'a, T>
 where
     T: TableRow,
 {
     fn eq(&self, other: &PrimaryTableCursor<T>) -> bool {
         self.value == other.value
-            && self.code == other.code
-            && self.scope == other.scope
-            && self.table == other.table
+            && self.index.code == other.index.code
+            && self.index.scope == other.index.scope
+            && self.index.name == other.index.name
+    }
+}
+
+impl<'a, T> fmt::Display for PrimaryTableCursor<'a, T>
+where
+    T: TableRow,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "PrimaryTableCursor({})", self.value)
     }
 }
 
 #[cfg(feature = "contract")]
-impl<T> Print for PrimaryTableCursor<T>
+impl<'a, T> Print for PrimaryTableCursor<'a, T>
 where
     T: TableRow,
 {
@@ -41,135 +56,112 @@ where
     }
 }
 
-impl<T> TableCursor<T> for PrimaryTableCursor<T>
+impl<'a, T> TableCursor<T> for PrimaryTableCursor<'a, T>
 where
     T: TableRow,
 {
     fn get(&self) -> Result<T, ReadError> {
-        let nullptr: *mut c_void = ::std::ptr::null_mut() as *mut _ as *mut c_void;
-        let size = unsafe { ::eosio_sys::db_get_i64(self.value, nullptr, 0) };
-        let mut bytes = vec![0u8; size as usize];
-        let ptr: *mut c_void = &mut bytes[..] as *mut _ as *mut c_void;
-        unsafe {
-            ::eosio_sys::db_get_i64(self.value, ptr, size as u32);
-        }
-        T::read(&bytes, 0).map(|(t, _)| t)
+        self.index.read_row(self.value, self.cache_reads)
     }
 
     fn erase(&self) -> Result<T, ReadError> {
         let item = self.get()?;
-        let pk = item.primary_key();
-        unsafe {
-            ::eosio_sys::db_remove_i64(self.value);
-        }
-
-        for (i, k) in item.secondary_keys().iter().enumerate() {
-            if let Some(k) = k {
-                let table = crate::table_secondary::SecondaryTableName::new(self.table, i);
-                let end = k.end(self.code, self.scope, table);
-                let itr = k.find_primary(self.code, self.scope, table, pk);
-                if itr != end {
-                    k.erase(itr);
-                }
-            }
-        }
+        self.index.remove_row(self.value, &item);
         Ok(item)
     }
 
     fn modify(&self, payer: Option<AccountName>, item: &T) -> Result<usize, WriteError> {
-        let table = PrimaryTableIndex::new(self.code, self.scope, self.table);
-        table.modify(&self, payer, item)
+        self.index.modify(&self, payer, item)
+    }
+
+    fn update<F>(&self, payer: Option<AccountName>, f: F) -> Result<T, WriteError>
+    where
+        F: FnOnce(&mut T),
+    {
+        let mut item = self.get().assert("failed to read table row");
+        let pk = item.primary_key();
+        let keys = item.secondary_keys();
+        f(&mut item);
+        eosio_assert(
+            item.primary_key() == pk,
+            "cannot change the primary key of a row",
+        );
+        self.index.update(&self, payer, &keys, &item)?;
+        Ok(item)
     }
 }
 
-impl<'a, T> IntoIterator for PrimaryTableCursor<T>
+impl<'a, T> IntoIterator for PrimaryTableCursor<'a, T>
 where
     T: TableRow,
 {
     type Item = Self;
-    type IntoIter = PrimaryTableIterator<T>;
+    type IntoIter = PrimaryTableIterator<'a, T>;
     fn into_iter(self) -> Self::IntoIter {
-        let end = unsafe {
-            ::eosio_sys::db_end_i64(self.code.into(), self.scope.into(), self.table.into())
-        };
-        PrimaryTableIterator {
-            value: self.value,
-            end,
-            code: self.code,
-            scope: self.scope,
-            table: self.table,
-            _data: PhantomData,
-        }
+        self.index.iterator(self.value, self.index.end())
     }
 }
 
+/// Iterates over the rows between two db iterators. `front` is the next row
+/// to yield from the front and `back` is one past the next row to yield from
+/// the back, so the iterator is done when they meet.
 #[derive(Copy, Clone, Debug)]
-pub struct PrimaryTableIterator<T>
+pub struct PrimaryTableIterator<'a, T>
 where
     T: TableRow,
 {
-    value: i32,
-    end: i32,
-    code: AccountName,
-    scope: ScopeName,
-    table: TableName,
-    _data: PhantomData<T>,
+    front: i32,
+    back: i32,
+    index: &'a PrimaryTableIndex<T>,
 }
 
-impl<T> Iterator for PrimaryTableIterator<T>
+impl<'a, T> Iterator for PrimaryTableIterator<'a, T>
 where
     T: TableRow,
 {
-    type Item = PrimaryTableCursor<T>;
+    type Item = PrimaryTableCursor<'a, T>;
     fn next(&mut self) -> Option<Self::Item> {
-        if self.value == self.end {
+        if self.front == self.back {
             return None;
         }
 
         let cursor = PrimaryTableCursor {
-            value: self.value,
-            code: self.code,
-            scope: self.scope,
-            table: self.table,
-            _data: PhantomData,
+            value: self.front,
+            index: self.index,
+            cache_reads: false,
         };
-
         let mut pk = 0u64;
         let ptr: *mut u64 = &mut pk;
-        self.value = unsafe { ::eosio_sys::db_next_i64(self.value, ptr) };
+        self.front = unsafe { ::eosio_sys::db_next_i64(self.front, ptr) };
 
         Some(cursor)
     }
 }
 
-impl<T> DoubleEndedIterator for PrimaryTableIterator<T>
+impl<'a, T> DoubleEndedIterator for PrimaryTableIterator<'a, T>
 where
     T: TableRow,
 {
     fn next_back(&mut self) -> Option<Self::Item> {
-        if self.value == -1 {
+        if self.front == self.back {
             return None;
         }
 
-        let cursor = PrimaryTableCursor {
-            value: self.value,
-            code: self.code,
-            scope: self.scope,
-            table: self.table,
-            _data: PhantomData,
-        };
-
         let mut pk = 0u64;
         let ptr: *mut u64 = &mut pk;
-        self.value = unsafe { ::eosio_sys::db_previous_i64(self.value, ptr) };
+        self.back = unsafe { ::eosio_sys::db_previous_i64(self.back, ptr) };
 
-        Some(cursor)
+        Some(PrimaryTableCursor {
+            value: self.back,
+            index: self.index,
+            cache_reads: false,
+        })
     }
 }
 
-impl<T> TableIterator for PrimaryTableIterator<T> where T: TableRow {}
+impl<'a, T> TableIterator for PrimaryTableIterator<'a, T> where T: TableRow {}
 
-#[derive(Copy, Clone, Debug)]
+#[derive(Clone, Debug)]
 pub struct PrimaryTableIndex<T>
 where
     T: TableRow,
@@ -177,65 +169,139 @@ where
     code: AccountName,
     scope: ScopeName,
     name: TableName,
+    /// The next available primary key, once it has been looked up. Rows
+    /// emplaced through other indexes of the same table aren't seen here.
+    next_primary_key: Cell<Option<u64>>,
+    cache: Option<Rc<RowCache>>,
     _data: PhantomData<T>,
 }
 
+/// A table, as `(code, scope, name)`
+type TableId = (u64, u64, u64);
+
+/// The most rows one cache keeps. The least recently used row makes room.
+const ROW_CACHE_CAPACITY: usize = 32;
+
+thread_local! {
+    /// How many times each table has been written in this action. A cache
+    /// drops its rows once the table is written through another index, so
+    /// it never returns a stale row.
+    static TABLE_WRITES: RefCell<BTreeMap<TableId, u64>> = RefCell::new(BTreeMap::new());
+}
+
+fn table_writes(table: TableId) -> u64 {
+    TABLE_WRITES.with(|writes| writes.borrow().get(&table).cloned().unwrap_or(0))
+}
+
+/// The rows read through an index created `with_cache`, keyed by primary key.
+/// Writes go straight to the database, so the cache only saves reads.
+#[derive(Debug, Default)]
+pub(crate) struct RowCache {
+    rows: RefCell<BTreeMap<u64, CachedRow>>,
+    /// The primary key of each cached db iterator
+    keys: RefCell<BTreeMap<i32, u64>>,
+    /// The table's write count when the rows were last known to be current
+    writes: Cell<u64>,
+    /// Counts row accesses, to find the least recently used row
+    clock: Cell<u64>,
+}
+
+#[derive(Debug)]
+struct CachedRow {
+    itr: i32,
+    bytes: Vec<u8>,
+    used: u64,
+}
+
+impl RowCache {
+    /// Drops the rows if the table was written through another index
+    fn sync(&self, table: TableId) {
+        let writes = table_writes(table);
+        if self.writes.get() != writes {
+            self.rows.borrow_mut().clear();
+            self.keys.borrow_mut().clear();
+            self.writes.set(writes);
+        }
+    }
+
+    fn tick(&self) -> u64 {
+        self.clock.set(self.clock.get() + 1);
+        self.clock.get()
+    }
+
+    fn find(&self, table: TableId, pk: u64) -> Option<i32> {
+        self.sync(table);
+        self.rows.borrow().get(&pk).map(|row| row.itr)
+    }
+
+    /// Reads the row at `itr`, if it's cached
+    fn read<T: Read>(&self, table: TableId, itr: i32) -> Option<Result<T, ReadError>> {
+        self.sync(table);
+        let pk = *self.keys.borrow().get(&itr)?;
+        let used = self.tick();
+        let mut rows = self.rows.borrow_mut();
+        let row = rows.get_mut(&pk)?;
+        row.used = used;
+        Some(T::read(&row.bytes, 0).map(|(t, _)| t))
+    }
+
+    fn is_unchanged(&self, table: TableId, itr: i32, bytes: &[u8]) -> bool {
+        self.sync(table);
+        let pk = match self.keys.borrow().get(&itr) {
+            Some(pk) => *pk,
+            None => return false,
+        };
+        self.rows
+            .borrow()
+            .get(&pk)
+            .map_or(false, |row| row.bytes[..] == bytes[..])
+    }
+
+    fn insert(&self, pk: u64, itr: i32, bytes: Vec<u8>) {
+        let used = self.tick();
+        let mut rows = self.rows.borrow_mut();
+        let mut keys = self.keys.borrow_mut();
+        if !rows.contains_key(&pk) && rows.len() >= ROW_CACHE_CAPACITY {
+            let oldest = rows
+                .iter()
+                .min_by_key(|(_, row)| row.used)
+                .map(|(pk, row)| (*pk, row.itr));
+            if let Some((pk, itr)) = oldest {
+                rows.remove(&pk);
+                keys.remove(&itr);
+            }
+        }
+        keys.insert(itr, pk);
+        rows.insert(pk, CachedRow { itr, bytes, used });
+    }
+
+    fn remove(&self, itr: i32) {
+        if let Some(pk) = self.keys.borrow_mut().remove(&itr) {
+            self.rows.borrow_mut().remove(&pk);
+        }
+    }
+}
+
 impl<'a, T> TableIndex<'a, u64, T> for PrimaryTableIndex<T>
 where
     T: TableRow + 'a,
 {
-    type Cursor = PrimaryTableCursor<T>;
+    type Cursor = PrimaryTableCursor<'a, T>;
 
     fn lower_bound<N>(&'a self, key: N) -> Option<Self::Cursor>
     where
         N: Into<u64>,
     {
-        let itr = unsafe {
-            ::eosio_sys::db_lowerbound_i64(
-                self.code.into(),
-                self.scope.into(),
-                self.name.into(),
-                key.into(),
-            )
-        };
-        let end = self.end();
-        if itr != end {
-            Some(PrimaryTableCursor {
-                value: itr,
-                code: self.code,
-                scope: self.scope,
-                table: self.name,
-                _data: self._data,
-            })
-        } else {
-            None
-        }
+        let itr = self.lower_bound_itr(key.into());
+        self.cursor(itr)
     }
 
     fn upper_bound<N>(&'a self, key: N) -> Option<Self::Cursor>
     where
         N: Into<u64>,
     {
-        let itr = unsafe {
-            ::eosio_sys::db_upperbound_i64(
-                self.code.into(),
-                self.scope.into(),
-                self.name.into(),
-                key.into(),
-            )
-        };
-        let end = self.end();
-        if itr != end {
-            Some(PrimaryTableCursor {
-                value: itr,
-                code: self.code,
-                scope: self.scope,
-                table: self.name,
-                _data: self._data,
-            })
-        } else {
-            None
-        }
+        let itr = self.upper_bound_itr(key.into());
+        self.cursor(itr)
     }
 
     fn emplace(&self, payer: AccountName, item: &T) -> Result<(), WriteError> {
@@ -244,7 +310,7 @@ where
         let mut bytes = vec![0u8; size];
         let pos = item.write(&mut bytes, 0)?;
         let ptr: *const c_void = &bytes[..] as *const _ as *const c_void;
-        unsafe {
+        let itr = unsafe {
             ::eosio_sys::db_store_i64(
                 self.scope.into(),
                 self.name.into(),
@@ -254,15 +320,24 @@ where
                 pos as u32,
             )
         };
+        bytes.truncate(pos);
+        self.record_write();
+        self.cache_row(itr, id, bytes);
 
         // store secondary indexes
         for (i, k) in item.secondary_keys().iter().enumerate() {
             if let Some(k) = k {
-                let table = crate::table_secondary::SecondaryTableName::new(self.name, i);
+                let table = SecondaryTableName::new(self.name, i);
                 k.store(self.scope, table, payer, id);
             }
         }
 
+        if let Some(next) = self.next_primary_key.get() {
+            if id >= next {
+                self.next_primary_key.set(id.checked_add(1));
+            }
+        }
+
         Ok(())
     }
 }
@@ -281,25 +356,114 @@ where
             code: code.into(),
             scope: scope.into(),
             name: name.into(),
+            next_primary_key: Cell::new(None),
+            cache: None,
             _data: PhantomData,
         }
     }
 
-    pub fn begin(&self) -> Option<PrimaryTableCursor<T>> {
+    /// Keeps the rows found by primary key or emplaced through this index for
+    /// the rest of the action, like the object cache of `eosio::multi_index`,
+    /// up to 32 rows. Writes still go straight to the database, and writes
+    /// through other indexes of the table clear the cache. Clones of the
+    /// index share its cache, and so do secondary indexes created
+    /// `with_cache_of` it.
+    pub fn with_cache(mut self) -> Self {
+        self.cache = Some(Rc::new(RowCache::default()));
+        self
+    }
+
+    pub(crate) fn row_cache(&self) -> Option<Rc<RowCache>> {
+        self.cache.clone()
+    }
+
+    pub(crate) fn with_row_cache(mut self, cache: Option<Rc<RowCache>>) -> Self {
+        self.cache = cache;
+        self
+    }
+
+    fn table_id(&self) -> TableId {
+        (self.code.into(), self.scope.into(), self.name.into())
+    }
+
+    /// Counts a write to the table. This index's cache stays current, while
+    /// the caches of other indexes are cleared.
+    fn record_write(&self) {
+        let table = self.table_id();
+        if let Some(ref cache) = self.cache {
+            cache.sync(table);
+        }
+        let writes = TABLE_WRITES.with(|writes| {
+            let mut writes = writes.borrow_mut();
+            let count = writes.entry(table).or_insert(0);
+            *count += 1;
+            *count
+        });
+        if let Some(ref cache) = self.cache {
+            cache.writes.set(writes);
+        }
+    }
+
+    pub fn begin(&self) -> Option<PrimaryTableCursor<'_, T>> {
         self.lower_bound(::std::u64::MIN)
     }
 
-    pub fn iter(&self) -> PrimaryTableIterator<T> {
+    pub fn iter(&self) -> PrimaryTableIterator<'_, T> {
+        self.range(..)
+    }
+
+    /// The rows with a primary key in `range`, in order
+    pub fn range<R>(&self, range: R) -> PrimaryTableIterator<'_, T>
+    where
+        R: RangeBounds<u64>,
+    {
+        let end = self.end();
+        if range_is_empty(range.start_bound(), range.end_bound()) {
+            return self.iterator(end, end);
+        }
+        let start = match range.start_bound() {
+            Bound::Included(key) => self.lower_bound_itr(*key),
+            Bound::Excluded(key) => self.upper_bound_itr(*key),
+            Bound::Unbounded => self.lower_bound_itr(::std::u64::MIN),
+        };
+        let stop = match range.end_bound() {
+            Bound::Included(key) => self.upper_bound_itr(*key),
+            Bound::Excluded(key) => self.lower_bound_itr(*key),
+            Bound::Unbounded => end,
+        };
+        self.iterator(start, stop)
+    }
+
+    /// The rows from last to first
+    pub fn rev_iter(&self) -> Rev<PrimaryTableIterator<'_, T>> {
+        self.iter().rev()
+    }
+
+    /// Reads every row in order
+    pub fn iter_rows(&self) -> TableRows<PrimaryTableIterator<'_, T>, T> {
+        self.iter().rows()
+    }
+
+    /// The row with the lowest primary key
+    pub fn first(&self) -> Option<PrimaryTableCursor<'_, T>> {
         self.begin()
-            .map(|c| c.into_iter())
-            .unwrap_or_else(|| PrimaryTableIterator {
-                value: 0,
-                end: 0,
-                code: self.code,
-                scope: self.scope,
-                table: self.name,
-                _data: PhantomData,
-            })
+    }
+
+    /// The row with the highest primary key
+    pub fn last(&self) -> Option<PrimaryTableCursor<'_, T>> {
+        // A table that doesn't exist yet has an end of -1, which
+        // `db_previous_i64` aborts on
+        let end = self.end();
+        if end == -1 {
+            return None;
+        }
+        let mut pk = 0u64;
+        let itr = unsafe { ::eosio_sys::db_previous_i64(end, &mut pk) };
+        if itr < 0 {
+            None
+        } else {
+            self.cursor(itr)
+        }
     }
 
     pub fn count(&self) -> usize {
@@ -317,70 +481,361 @@ where
         unsafe { ::eosio_sys::db_end_i64(self.code.into(), self.scope.into(), self.name.into()) }
     }
 
-    pub fn find<Id>(&self, id: Id) -> Option<PrimaryTableCursor<T>>
-    where
-        Id: Into<u64>,
-    {
-        let itr = unsafe {
-            ::eosio_sys::db_find_i64(
+    fn lower_bound_itr(&self, key: u64) -> i32 {
+        unsafe {
+            ::eosio_sys::db_lowerbound_i64(
                 self.code.into(),
                 self.scope.into(),
                 self.name.into(),
-                id.into(),
+                key,
             )
-        };
-        let end = self.end();
-        if itr == end {
+        }
+    }
+
+    fn upper_bound_itr(&self, key: u64) -> i32 {
+        unsafe {
+            ::eosio_sys::db_upperbound_i64(
+                self.code.into(),
+                self.scope.into(),
+                self.name.into(),
+                key,
+            )
+        }
+    }
+
+    fn cursor(&self, itr: i32) -> Option<PrimaryTableCursor<'_, T>> {
+        if itr == self.end() {
             None
         } else {
             Some(PrimaryTableCursor {
                 value: itr,
-                code: self.code,
-                scope: self.scope,
-                table: self.name,
-                _data: self._data,
+                index: self,
+                cache_reads: false,
             })
         }
     }
 
+    fn iterator(&self, front: i32, back: i32) -> PrimaryTableIterator<'_, T> {
+        PrimaryTableIterator {
+            front,
+            back,
+            index: self,
+        }
+    }
+
+    pub fn find<Id>(&self, id: Id) -> Option<PrimaryTableCursor<'_, T>>
+    where
+        Id: Into<u64>,
+    {
+        let id = id.into();
+        let table = self.table_id();
+        let cached = self.cache.as_ref().and_then(|cache| cache.find(table, id));
+        let itr = match cached {
+            Some(itr) => itr,
+            None => unsafe {
+                ::eosio_sys::db_find_i64(self.code.into(), self.scope.into(), self.name.into(), id)
+            },
+        };
+        self.cursor(itr).map(|cursor| PrimaryTableCursor {
+            cache_reads: true,
+            ..cursor
+        })
+    }
+
+    /// Emplaces the row that `f` builds from the next available primary key,
+    /// e.g. for auto-incrementing IDs. Aborts the action if the table has run
+    /// out of keys or `f` doesn't use the key it was given.
+    pub fn emplace_with<F>(&self, payer: AccountName, f: F) -> Result<T, WriteError>
+    where
+        F: FnOnce(u64) -> T,
+    {
+        let id = self
+            .available_primary_key()
+            .assert("no available primary key");
+        let item = f(id);
+        eosio_assert(
+            item.primary_key() == id,
+            "emplace_with row must use the given primary key",
+        );
+        self.emplace(payer, &item)?;
+        Ok(item)
+    }
+
+    /// One more than the highest primary key in the table, or `None` if
+    /// `u64::MAX` is taken. Keys freed by erasing the last row aren't reused
+    /// until the index is created again.
     pub fn available_primary_key(&self) -> Option<u64> {
-        if self.begin().is_none() {
-            return Some(0);
+        if let Some(next) = self.next_primary_key.get() {
+            return Some(next);
+        }
+
+        // `db_previous_i64` aborts on the end iterator of a table that
+        // doesn't exist yet, so empty tables are checked first
+        let next = if self.begin().is_none() {
+            Some(0)
+        } else {
+            let mut pk = 0u64;
+            let ptr: *mut u64 = &mut pk;
+            unsafe { ::eosio_sys::db_previous_i64(self.end(), ptr) };
+            pk.checked_add(1)
+        };
+        self.next_primary_key.set(next);
+        next
+    }
+
+    fn modify(
+        &self,
+        itr: &PrimaryTableCursor<'_, T>,
+        payer: Option<AccountName>,
+        item: &T,
+    ) -> Result<usize, WriteError> {
+        let (pos, changed) = self.write_row(itr, payer, item)?;
+        if !changed {
+            return Ok(pos);
         }
+        let payer = payer.unwrap_or_else(|| 0u64.into());
+        let pk = item.primary_key();
+
+        for (i, k) in item.secondary_keys().iter().enumerate() {
+            if let Some(k) = k {
+                let table = SecondaryTableName::new(self.name, i);
+                k.upsert(self.code, self.scope, table, payer, pk);
+            }
+        }
+
+        Ok(pos)
+    }
+
+    /// Like `modify`, but only touches the secondary keys that differ from
+    /// `old_keys`, unless the payer changes
+    fn update(
+        &self,
+        itr: &PrimaryTableCursor<'_, T>,
+        payer: Option<AccountName>,
+        old_keys: &[Option<SecondaryKey>; 16],
+        item: &T,
+    ) -> Result<usize, WriteError> {
+        let (pos, changed) = self.write_row(itr, payer, item)?;
+        if !changed {
+            return Ok(pos);
+        }
+        let pk = item.primary_key();
 
+        for (i, (old, new)) in old_keys
+            .iter()
+            .zip(item.secondary_keys().iter())
+            .enumerate()
+        {
+            if let (Some(old), Some(new)) = (old, new) {
+                if payer.is_none() && old == new {
+                    continue;
+                }
+                let table = SecondaryTableName::new(self.name, i);
+                let payer = payer.unwrap_or_else(|| 0u64.into());
+                let end = old.end(self.code, self.scope, table);
+                let itr = old.find_primary(self.code, self.scope, table, pk);
+                if itr == end {
+                    new.upsert(self.code, self.scope, table, payer, pk);
+                } else {
+                    new.modify(itr, payer);
+                }
+            }
+        }
+
+        Ok(pos)
+    }
+
+    /// Erases the rows with a primary key in `range`, at most `budget` of
+    /// them. Returns the key to continue from in a later action, or `None`
+    /// once the range is empty.
+    pub fn erase_range<R>(&self, range: R, budget: usize) -> Option<u64>
+    where
+        R: RangeBounds<u64>,
+    {
+        self.retain(range, budget, |_| false)
+    }
+
+    /// Erases every row, at most `budget` of them, like `erase_range(.., budget)`
+    pub fn clear(&self, budget: usize) -> Option<u64> {
+        self.erase_range(.., budget)
+    }
+
+    /// Erases the rows in `range` for which `f` returns `false`, checking at
+    /// most `budget` rows. Returns the key to continue from in a later
+    /// action, or `None` once the whole range has been checked.
+    pub fn retain<R, F>(&self, range: R, budget: usize, mut f: F) -> Option<u64>
+    where
+        R: RangeBounds<u64>,
+        F: FnMut(&T) -> bool,
+    {
+        let mut pk = match range.start_bound() {
+            Bound::Included(key) => *key,
+            Bound::Excluded(key) => key.saturating_add(1),
+            Bound::Unbounded => ::std::u64::MIN,
+        };
+        let rows = self.range(range);
+        let (mut itr, end) = (rows.front, rows.back);
+        for _ in 0..budget {
+            if itr == end {
+                return None;
+            }
+            let item = self.read_row(itr, false).assert("failed to read table row");
+            let ptr: *mut u64 = &mut pk;
+            let next = unsafe { ::eosio_sys::db_next_i64(itr, ptr) };
+            if !f(&item) {
+                self.remove_row(itr, &item);
+            }
+            itr = next;
+        }
+        if itr == end {
+            None
+        } else {
+            Some(pk)
+        }
+    }
+
+    /// Rewrites rows stored in an older version, checking up to `limit` rows
+    /// from primary key `from`. Returns the key to continue from in a later
+    /// action, or `None` once the whole table has been checked.
+    pub fn migrate(&self, from: u64, limit: usize) -> Option<u64>
+    where
+        T: VersionedRow,
+    {
         let end = self.end();
-        let mut pk = 0u64;
-        let ptr: *mut u64 = &mut pk;
-        unsafe { ::eosio_sys::db_previous_i64(end, ptr) };
-        if pk == ::std::u64::MAX {
+        let mut itr = self.lower_bound_itr(from);
+        let mut pk = from;
+        for _ in 0..limit {
+            if itr == end {
+                return None;
+            }
+            let mut version = 0u8;
+            let ptr: *mut c_void = &mut version as *mut _ as *mut c_void;
+            unsafe { ::eosio_sys::db_get_i64(itr, ptr, 1) };
+            if version != T::VERSION {
+                let cursor = PrimaryTableCursor {
+                    value: itr,
+                    index: self,
+                    cache_reads: false,
+                };
+                let item = cursor.get().assert("failed to read table row");
+                cursor
+                    .modify(None, &item)
+                    .assert("failed to write table row");
+            }
+            let ptr: *mut u64 = &mut pk;
+            itr = unsafe { ::eosio_sys::db_next_i64(itr, ptr) };
+        }
+        if itr == end {
             None
         } else {
-            Some(pk + 1)
+            Some(pk)
         }
     }
 
-    fn modify(
+    /// Moves rows written before the table was versioned from `old`, the
+    /// untagged table they were stored in, converting them with `f`. Moves
+    /// at most `limit` rows, with `payer` paying for the new ones. Returns the
+    /// key of the next row to move in a later action, or `None` once `old` is
+    /// empty.
+    pub fn migrate_untagged<O, F>(
         &self,
-        itr: &PrimaryTableCursor<T>,
+        old: &PrimaryTableIndex<O>,
+        payer: AccountName,
+        limit: usize,
+        mut f: F,
+    ) -> Option<u64>
+    where
+        T: VersionedRow,
+        O: TableRow,
+        F: FnMut(O) -> T,
+    {
+        for _ in 0..limit {
+            let cursor = old.begin()?;
+            let item = cursor.erase().assert("failed to read table row");
+            self.emplace(payer, &f(item))
+                .assert("failed to write table row");
+        }
+        old.begin().map(|cursor| {
+            cursor
+                .get()
+                .assert("failed to read table row")
+                .primary_key()
+        })
+    }
+
+    /// Writes the row, unless the cache shows it is unchanged and the payer
+    /// stays the same. Returns the size of the row and whether it was written.
+    fn write_row(
+        &self,
+        itr: &PrimaryTableCursor<'_, T>,
         payer: Option<AccountName>,
         item: &T,
-    ) -> Result<usize, WriteError> {
+    ) -> Result<(usize, bool), WriteError> {
         let size = item.num_bytes();
         let mut bytes = vec![0u8; size];
         let pos = item.write(&mut bytes, 0)?;
+        bytes.truncate(pos);
+        if let (None, Some(cache)) = (payer, &self.cache) {
+            if cache.is_unchanged(self.table_id(), itr.value, &bytes) {
+                return Ok((pos, false));
+            }
+        }
         let ptr: *const c_void = &bytes[..] as *const _ as *const c_void;
         let payer = payer.unwrap_or_else(|| 0u64.into());
         unsafe { ::eosio_sys::db_update_i64(itr.value, payer.into(), ptr, pos as u32) }
+        self.record_write();
+        self.cache_row(itr.value, item.primary_key(), bytes);
+        Ok((pos, true))
+    }
 
-        let pk = item.primary_key();
+    /// Reads a row from the cache, or from the database, keeping it in the
+    /// cache if `cache_read`
+    fn read_row(&self, itr: i32, cache_read: bool) -> Result<T, ReadError> {
+        if let Some(ref cache) = self.cache {
+            if let Some(item) = cache.read(self.table_id(), itr) {
+                return item;
+            }
+        }
+        let nullptr: *mut c_void = ::std::ptr::null_mut() as *mut _ as *mut c_void;
+        let size = unsafe { ::eosio_sys::db_get_i64(itr, nullptr, 0) };
+        let mut bytes = vec![0u8; size as usize];
+        let ptr: *mut c_void = &mut bytes[..] as *mut _ as *mut c_void;
+        unsafe {
+            ::eosio_sys::db_get_i64(itr, ptr, size as u32);
+        }
+        let item = T::read(&bytes, 0).map(|(t, _)| t)?;
+        if cache_read {
+            self.cache_row(itr, item.primary_key(), bytes);
+        }
+        Ok(item)
+    }
 
+    /// Removes a row and its secondary keys
+    fn remove_row(&self, itr: i32, item: &T) {
+        unsafe {
+            ::eosio_sys::db_remove_i64(itr);
+        }
+        self.record_write();
+        if let Some(ref cache) = self.cache {
+            cache.remove(itr);
+        }
+
+        let pk = item.primary_key();
         for (i, k) in item.secondary_keys().iter().enumerate() {
             if let Some(k) = k {
-                let table = crate::table_secondary::SecondaryTableName::new(self.name, i);
-                k.upsert(self.code, self.scope, table, payer, pk);
+                let table = SecondaryTableName::new(self.name, i);
+                let end = k.end(self.code, self.scope, table);
+                let itr = k.find_primary(self.code, self.scope, table, pk);
+                if itr != end {
+                    k.erase(itr);
+                }
             }
         }
+    }
 
-        Ok(pos)
+    fn cache_row(&self, itr: i32, pk: u64, bytes: Vec<u8>) {
+        if let Some(ref cache) = self.cache {
+            cache.insert(pk, itr, bytes);
+        }
     }
 }
@@ -1,10 +1,13 @@
 use crate::account::AccountName;
-use crate::bytes::{ReadError, WriteError};
-use crate::lib::PhantomData;
+use crate::bytes::{Float128, ReadError, WriteError};
+use crate::crypto::Sha256;
+use crate::lib::iter::Rev;
+use crate::lib::{Bound, PhantomData, RangeBounds};
+use crate::symbol::SymbolName;
 use crate::table::*;
 use crate::table_primary::*;
 use crate::time::Time;
-use eosio_sys::ctypes::*;
+use std::rc::Rc;
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy, Default, Hash, PartialOrd, Ord)]
 pub struct SecondaryTableName(TableName, usize);
@@ -56,6 +59,9 @@ pub trait SecondaryTableKey {
         table: SecondaryTableName,
     ) -> (i32, u64);
 
+    /// The first entry in the index, like `lower_bound` of the lowest key
+    fn begin(&self, code: AccountName, scope: ScopeName, table: SecondaryTableName) -> (i32, u64);
+
     fn find_primary(
         &self,
         code: AccountName,
@@ -89,20 +95,21 @@ pub trait SecondaryTableKey {
     }
 }
 
+/// Keys that are stored as another type, see `SecondaryKey`'s `From` impls
 macro_rules! secondary_keys_converted {
-    ($($to:ty, $from:ty)*) => ($(
-        impl SecondaryTableKey for $from {
+    ($($t:ty)*) => ($(
+        impl SecondaryTableKey for $t {
             fn end(&self, code: AccountName, scope: ScopeName, table: SecondaryTableName) -> i32 {
-                <$to as From<$from>>::from(*self).end(code, scope, table)
+                SecondaryKey::from(*self).end(code, scope, table)
             }
             fn next(&self, iterator: i32) -> (i32, u64) {
-                <$to as From<$from>>::from(*self).next(iterator)
+                SecondaryKey::from(*self).next(iterator)
             }
             fn previous(&self, iterator: i32) -> (i32, u64) {
-                <$to as From<$from>>::from(*self).previous(iterator)
+                SecondaryKey::from(*self).previous(iterator)
             }
             fn erase(&self, iterator: i32) {
-                <$to as From<$from>>::from(*self).erase(iterator)
+                SecondaryKey::from(*self).erase(iterator)
             }
             fn store(
                 &self,
@@ -111,10 +118,10 @@ macro_rules! secondary_keys_converted {
                 payer: AccountName,
                 id: u64,
             ) -> i32 {
-                <$to as From<$from>>::from(*self).store(scope, table, payer, id)
+                SecondaryKey::from(*self).store(scope, table, payer, id)
             }
             fn modify(&self, iterator: i32, payer: AccountName) {
-                <$to as From<$from>>::from(*self).modify(iterator, payer)
+                SecondaryKey::from(*self).modify(iterator, payer)
             }
             fn lower_bound(
                 &self,
@@ -122,7 +129,7 @@ macro_rules! secondary_keys_converted {
                 scope: ScopeName,
                 table: SecondaryTableName,
             ) -> (i32, u64) {
-                <$to as From<$from>>::from(*self).lower_bound(code, scope, table)
+                SecondaryKey::from(*self).lower_bound(code, scope, table)
             }
             fn upper_bound(
                 &self,
@@ -130,7 +137,15 @@ macro_rules! secondary_keys_converted {
                 scope: ScopeName,
                 table: SecondaryTableName,
             ) -> (i32, u64) {
-                <$to as From<$from>>::from(*self).upper_bound(code, scope, table)
+                SecondaryKey::from(*self).upper_bound(code, scope, table)
+            }
+            fn begin(
+                &self,
+                code: AccountName,
+                scope: ScopeName,
+                table: SecondaryTableName,
+            ) -> (i32, u64) {
+                SecondaryKey::from(*self).begin(code, scope, table)
             }
             fn find_primary(
                 &self,
@@ -139,7 +154,7 @@ macro_rules! secondary_keys_converted {
                 table: SecondaryTableName,
                 primary: u64,
             ) -> i32 {
-                 <$to as From<$from>>::from(*self).find_primary(code, scope, table, primary)
+                 SecondaryKey::from(*self).find_primary(code, scope, table, primary)
             }
             fn find_secondary(
                 &self,
@@ -147,14 +162,14 @@ macro_rules! secondary_keys_converted {
                 scope: ScopeName,
                 table: SecondaryTableName,
             ) -> (i32, u64) {
-                <$to as From<$from>>::from(*self).find_secondary(code, scope, table)
+                SecondaryKey::from(*self).find_secondary(code, scope, table)
             }
         }
     )*)
 }
 
 macro_rules! secondary_keys_impl {
-    ($($t:ty, $i:ident)*) => ($(
+    ($($t:ty, $i:ident, $lowest:expr)*) => ($(
         impl SecondaryTableKey for $t {
             fn end(&self, code: AccountName, scope: ScopeName, table: SecondaryTableName) -> i32 {
                 use ::eosio_sys::*;
@@ -188,14 +203,14 @@ macro_rules! secondary_keys_impl {
                 use ::eosio_sys::*;
                 let secondary: *const Self = self;
                 unsafe {
-                    concat_idents!(db_, $i, _store)(scope.into(), table.into(), payer.into(), id, secondary)
+                    concat_idents!(db_, $i, _store)(scope.into(), table.into(), payer.into(), id, secondary as *const _)
                 }
             }
             fn modify(&self, iterator: i32, payer: AccountName) {
                 use ::eosio_sys::*;
                 let secondary: *const Self = self;
                 unsafe {
-                    concat_idents!(db_, $i, _update)(iterator, payer.into(), secondary)
+                    concat_idents!(db_, $i, _update)(iterator, payer.into(), secondary as *const _)
                 }
             }
             fn lower_bound(
@@ -212,7 +227,7 @@ macro_rules! secondary_keys_impl {
                         code.into(),
                         scope.into(),
                         table.into(),
-                        &mut sk as *mut $t,
+                        &mut sk as *mut $t as *mut _,
                         &mut pk as *mut u64,
                     )
                 };
@@ -232,12 +247,21 @@ macro_rules! secondary_keys_impl {
                         code.into(),
                         scope.into(),
                         table.into(),
-                        &mut sk as *mut $t,
+                        &mut sk as *mut $t as *mut _,
                         &mut pk as *mut u64,
                     )
                 };
                 (itr, pk)
             }
+            fn begin(
+                &self,
+                code: AccountName,
+                scope: ScopeName,
+                table: SecondaryTableName,
+            ) -> (i32, u64) {
+                let lowest: $t = $lowest;
+                lowest.lower_bound(code, scope, table)
+            }
             fn find_primary(
                 &self,
                 code: AccountName,
@@ -252,7 +276,7 @@ macro_rules! secondary_keys_impl {
                         code.into(),
                         scope.into(),
                         table.into(),
-                        &mut sk as *mut $t,
+                        &mut sk as *mut $t as *mut _,
                         primary,
                     )
                 }
@@ -271,7 +295,7 @@ macro_rules! secondary_keys_impl {
                         code.into(),
                         scope.into(),
                         table.into(),
-                        secondary,
+                        secondary as *const _,
                         &mut pk as *mut u64,
                     )
                 };
@@ -281,21 +305,228 @@ macro_rules! secondary_keys_impl {
     )*)
 }
 
+/// Calls `$call` on the key inside a `SecondaryKey`
+macro_rules! with_secondary_key {
+    ($key:expr, $k:ident => $call:expr) => {
+        match $key {
+            SecondaryKey::U64($k) => $call,
+            SecondaryKey::F64($k) => $call,
+            SecondaryKey::U128($k) => $call,
+            SecondaryKey::H256($k) => $call,
+            SecondaryKey::F128($k) => $call,
+        }
+    };
+}
+
+impl SecondaryTableKey for SecondaryKey {
+    fn end(&self, code: AccountName, scope: ScopeName, table: SecondaryTableName) -> i32 {
+        with_secondary_key!(self, k => k.end(code, scope, table))
+    }
+    fn next(&self, iterator: i32) -> (i32, u64) {
+        with_secondary_key!(self, k => k.next(iterator))
+    }
+    fn previous(&self, iterator: i32) -> (i32, u64) {
+        with_secondary_key!(self, k => k.previous(iterator))
+    }
+    fn erase(&self, iterator: i32) {
+        with_secondary_key!(self, k => k.erase(iterator))
+    }
+    fn store(
+        &self,
+        scope: ScopeName,
+        table: SecondaryTableName,
+        payer: AccountName,
+        id: u64,
+    ) -> i32 {
+        with_secondary_key!(self, k => k.store(scope, table, payer, id))
+    }
+    fn modify(&self, iterator: i32, payer: AccountName) {
+        with_secondary_key!(self, k => k.modify(iterator, payer))
+    }
+    fn lower_bound(
+        &self,
+        code: AccountName,
+        scope: ScopeName,
+        table: SecondaryTableName,
+    ) -> (i32, u64) {
+        with_secondary_key!(self, k => k.lower_bound(code, scope, table))
+    }
+    fn upper_bound(
+        &self,
+        code: AccountName,
+        scope: ScopeName,
+        table: SecondaryTableName,
+    ) -> (i32, u64) {
+        with_secondary_key!(self, k => k.upper_bound(code, scope, table))
+    }
+    fn begin(&self, code: AccountName, scope: ScopeName, table: SecondaryTableName) -> (i32, u64) {
+        with_secondary_key!(self, k => k.begin(code, scope, table))
+    }
+    fn find_primary(
+        &self,
+        code: AccountName,
+        scope: ScopeName,
+        table: SecondaryTableName,
+        primary: u64,
+    ) -> i32 {
+        with_secondary_key!(self, k => k.find_primary(code, scope, table, primary))
+    }
+    fn find_secondary(
+        &self,
+        code: AccountName,
+        scope: ScopeName,
+        table: SecondaryTableName,
+    ) -> (i32, u64) {
+        with_secondary_key!(self, k => k.find_secondary(code, scope, table))
+    }
+}
+
 secondary_keys_impl!(
-    u64, idx64
-    f64, idx_double
-    // TODO: u128, idx128
-    // TODO: u256, idx256
-    // TODO: f128, idx_long_double
+    u64, idx64, 0
+    f64, idx_double, ::std::f64::NEG_INFINITY
+    u128, idx128, 0
+    Float128, idx_long_double, Float128::from(::std::f64::NEG_INFINITY)
 );
 
-secondary_keys_converted!(
-    u64, u8
-    u64, u16
-    u64, u32
-    f64, f32
-    u64, Time
-);
+secondary_keys_converted!(u8 u16 u32 i64 SymbolName Time f32);
+
+impl Sha256 {
+    /// The key of an `idx256` index: two big-endian words, so keys sort like
+    /// their bytes
+    fn to_words(&self) -> [u128; 2] {
+        let mut words = [0u128; 2];
+        for (i, byte) in self.as_bytes().iter().enumerate() {
+            words[i / 16] |= u128::from(*byte) << (8 * (15 - i % 16));
+        }
+        words
+    }
+}
+
+impl SecondaryTableKey for Sha256 {
+    fn end(&self, code: AccountName, scope: ScopeName, table: SecondaryTableName) -> i32 {
+        unsafe { ::eosio_sys::db_idx256_end(code.into(), scope.into(), table.into()) }
+    }
+    fn next(&self, iterator: i32) -> (i32, u64) {
+        let mut pk = 0u64;
+        let itr = unsafe { ::eosio_sys::db_idx256_next(iterator, &mut pk) };
+        (itr, pk)
+    }
+    fn previous(&self, iterator: i32) -> (i32, u64) {
+        let mut pk = 0u64;
+        let itr = unsafe { ::eosio_sys::db_idx256_previous(iterator, &mut pk) };
+        (itr, pk)
+    }
+    fn erase(&self, iterator: i32) {
+        unsafe { ::eosio_sys::db_idx256_remove(iterator) }
+    }
+    fn store(
+        &self,
+        scope: ScopeName,
+        table: SecondaryTableName,
+        payer: AccountName,
+        id: u64,
+    ) -> i32 {
+        let words = self.to_words();
+        unsafe {
+            ::eosio_sys::db_idx256_store(
+                scope.into(),
+                table.into(),
+                payer.into(),
+                id,
+                words.as_ptr() as *const _,
+                2,
+            )
+        }
+    }
+    fn modify(&self, iterator: i32, payer: AccountName) {
+        let words = self.to_words();
+        unsafe {
+            ::eosio_sys::db_idx256_update(iterator, payer.into(), words.as_ptr() as *const _, 2)
+        }
+    }
+    fn lower_bound(
+        &self,
+        code: AccountName,
+        scope: ScopeName,
+        table: SecondaryTableName,
+    ) -> (i32, u64) {
+        let mut pk = 0u64;
+        let mut words = self.to_words();
+        let itr = unsafe {
+            ::eosio_sys::db_idx256_lowerbound(
+                code.into(),
+                scope.into(),
+                table.into(),
+                words.as_mut_ptr() as *mut _,
+                2,
+                &mut pk,
+            )
+        };
+        (itr, pk)
+    }
+    fn upper_bound(
+        &self,
+        code: AccountName,
+        scope: ScopeName,
+        table: SecondaryTableName,
+    ) -> (i32, u64) {
+        let mut pk = 0u64;
+        let mut words = self.to_words();
+        let itr = unsafe {
+            ::eosio_sys::db_idx256_upperbound(
+                code.into(),
+                scope.into(),
+                table.into(),
+                words.as_mut_ptr() as *mut _,
+                2,
+                &mut pk,
+            )
+        };
+        (itr, pk)
+    }
+    fn begin(&self, code: AccountName, scope: ScopeName, table: SecondaryTableName) -> (i32, u64) {
+        Sha256::default().lower_bound(code, scope, table)
+    }
+    fn find_primary(
+        &self,
+        code: AccountName,
+        scope: ScopeName,
+        table: SecondaryTableName,
+        primary: u64,
+    ) -> i32 {
+        let mut words = self.to_words();
+        unsafe {
+            ::eosio_sys::db_idx256_find_primary(
+                code.into(),
+                scope.into(),
+                table.into(),
+                words.as_mut_ptr() as *mut _,
+                2,
+                primary,
+            )
+        }
+    }
+    fn find_secondary(
+        &self,
+        code: AccountName,
+        scope: ScopeName,
+        table: SecondaryTableName,
+    ) -> (i32, u64) {
+        let mut pk = 0u64;
+        let words = self.to_words();
+        let itr = unsafe {
+            ::eosio_sys::db_idx256_find_secondary(
+                code.into(),
+                scope.into(),
+                table.into(),
+                words.as_ptr() as *const _,
+                2,
+                &mut pk,
+            )
+        };
+        (itr, pk)
+    }
+}
 
 #[derive(Debug, Copy, Clone)]
 pub struct SecondaryTableCursor<'a, K, T>
@@ -314,22 +545,11 @@ where
     T: TableRow,
 {
     fn get(&self) -> Result<T, ReadError> {
-        let pk_itr = unsafe {
-            ::eosio_sys::db_find_i64(
-                self.index.code.into(),
-                self.index.scope.into(),
-                self.index.table.0.into(),
-                self.pk,
-            )
-        };
-        let nullptr: *mut c_void = ::std::ptr::null_mut() as *mut _ as *mut c_void;
-        let size = unsafe { ::eosio_sys::db_get_i64(self.value, nullptr, 0) };
-        let mut bytes = vec![0u8; size as usize];
-        let ptr: *mut c_void = &mut bytes[..] as *mut _ as *mut c_void;
-        unsafe {
-            ::eosio_sys::db_get_i64(pk_itr, ptr, size as u32);
+        let table = self.index.to_primary_index();
+        match table.find(self.pk) {
+            Some(cursor) => cursor.get(),
+            None => Err(ReadError::NotEnoughBytes), // TODO: better error
         }
-        T::read(&bytes, 0).map(|(t, _)| t)
     }
 
     fn erase(&self) -> Result<T, ReadError> {
@@ -347,6 +567,17 @@ where
             None => Err(WriteError::NotEnoughSpace), // TODO: better error
         }
     }
+
+    fn update<F>(&self, payer: Option<AccountName>, f: F) -> Result<T, WriteError>
+    where
+        F: FnOnce(&mut T),
+    {
+        let table = self.index.to_primary_index();
+        match table.find(self.pk) {
+            Some(cursor) => cursor.update(payer, f),
+            None => Err(WriteError::NotEnoughSpace), // TODO: better error
+        }
+    }
 }
 
 impl<'a, K, T> IntoIterator for SecondaryTableCursor<'a, K, T>
@@ -357,37 +588,22 @@ where
     type Item = Self;
     type IntoIter = SecondaryTableIterator<'a, K, T>;
     fn into_iter(self) -> Self::IntoIter {
-        let sk_end = self
-            .index
-            .key
-            .end(self.index.code, self.index.scope, self.index.table);
-        let pk_end = unsafe {
-            ::eosio_sys::db_end_i64(
-                self.index.code.into(),
-                self.index.scope.into(),
-                self.index.table.0.into(),
-            )
-        };
-        SecondaryTableIterator {
-            value: self.value,
-            pk: self.pk,
-            pk_end,
-            sk_end,
-            index: self.index,
-        }
+        let sk_end = self.index.end();
+        self.index.iterator((self.value, self.pk), sk_end)
     }
 }
 
+/// Iterates over the entries between two secondary db iterators, like
+/// [`PrimaryTableIterator`](struct.PrimaryTableIterator.html)
 #[derive(Copy, Clone, Debug)]
 pub struct SecondaryTableIterator<'a, K, T>
 where
     K: SecondaryTableKey,
     T: TableRow,
 {
-    value: i32,
-    pk: u64,
-    pk_end: i32,
-    sk_end: i32,
+    front: i32,
+    front_pk: u64,
+    back: i32,
     index: &'a SecondaryTableIndex<K, T>,
 }
 
@@ -398,18 +614,18 @@ where
 {
     type Item = SecondaryTableCursor<'a, K, T>;
     fn next(&mut self) -> Option<Self::Item> {
-        if self.value == self.sk_end {
+        if self.front == self.back {
             return None;
         }
 
         let cursor = SecondaryTableCursor {
-            value: self.value,
-            pk: self.pk,
+            value: self.front,
+            pk: self.front_pk,
             index: self.index,
         };
-        let (itr, pk) = self.index.key.next(self.value);
-        self.value = itr;
-        self.pk = pk;
+        let (itr, pk) = self.index.key.next(self.front);
+        self.front = itr;
+        self.front_pk = pk;
 
         Some(cursor)
     }
@@ -421,20 +637,18 @@ where
     T: TableRow,
 {
     fn next_back(&mut self) -> Option<Self::Item> {
-        if self.value == -1 {
+        if self.front == self.back {
             return None;
         }
 
-        let cursor = SecondaryTableCursor {
-            value: self.value,
-            pk: self.pk,
-            index: self.index,
-        };
-        let (itr, pk) = self.index.key.previous(self.value);
-        self.value = itr;
-        self.pk = pk;
+        let (itr, pk) = self.index.key.previous(self.back);
+        self.back = itr;
 
-        Some(cursor)
+        Some(SecondaryTableCursor {
+            value: itr,
+            pk,
+            index: self.index,
+        })
     }
 }
 
@@ -445,7 +659,7 @@ where
 {
 }
 
-#[derive(Copy, Clone, Debug)]
+#[derive(Clone, Debug)]
 pub struct SecondaryTableIndex<K, T>
 where
     K: SecondaryTableKey,
@@ -455,6 +669,7 @@ where
     scope: ScopeName,
     table: SecondaryTableName,
     key: K,
+    cache: Option<Rc<RowCache>>,
     _data: PhantomData<T>,
 }
 
@@ -480,12 +695,121 @@ where
             scope: scope.into(),
             table: SecondaryTableName(name.into(), index),
             key,
+            cache: None,
             _data: PhantomData,
         }
     }
 
+    /// Reads and writes rows through the row cache of a primary index of the
+    /// same table, if it was created `with_cache`
+    pub fn with_cache_of(mut self, index: &PrimaryTableIndex<T>) -> Self {
+        self.cache = index.row_cache();
+        self
+    }
+
+    /// The primary index of the table, sharing this index's row cache
     fn to_primary_index(&self) -> PrimaryTableIndex<T> {
         PrimaryTableIndex::new(self.code, self.scope, self.table.0)
+            .with_row_cache(self.cache.clone())
+    }
+
+    fn end(&self) -> i32 {
+        self.key.end(self.code, self.scope, self.table)
+    }
+
+    fn cursor(&self, (value, pk): (i32, u64)) -> Option<SecondaryTableCursor<'_, K, T>> {
+        if value == self.end() {
+            None
+        } else {
+            Some(SecondaryTableCursor {
+                value,
+                pk,
+                index: self,
+            })
+        }
+    }
+
+    fn iterator(
+        &self,
+        (front, front_pk): (i32, u64),
+        back: i32,
+    ) -> SecondaryTableIterator<'_, K, T> {
+        SecondaryTableIterator {
+            front,
+            front_pk,
+            back,
+            index: self,
+        }
+    }
+}
+
+impl<K, T> SecondaryTableIndex<K, T>
+where
+    K: SecondaryTableKey + Clone + Into<SecondaryKey>,
+    T: TableRow,
+{
+    pub fn iter(&self) -> SecondaryTableIterator<'_, K, T> {
+        self.range(..)
+    }
+
+    /// The rows with a secondary key in `range`, in index order. Rows with
+    /// the same key are ordered by primary key.
+    pub fn range<R>(&self, range: R) -> SecondaryTableIterator<'_, K, T>
+    where
+        R: RangeBounds<K>,
+    {
+        let end = self.end();
+        // Compare as stored, e.g. negative `i64` keys sort last
+        let stored = |bound: Bound<&K>| match bound {
+            Bound::Included(key) => Bound::Included(key.clone().into()),
+            Bound::Excluded(key) => Bound::Excluded(key.clone().into()),
+            Bound::Unbounded => Bound::Unbounded,
+        };
+        let start: Bound<SecondaryKey> = stored(range.start_bound());
+        if range_is_empty(start, stored(range.end_bound())) {
+            return self.iterator((end, 0), end);
+        }
+        let start = match range.start_bound() {
+            Bound::Included(key) => key.lower_bound(self.code, self.scope, self.table),
+            Bound::Excluded(key) => key.upper_bound(self.code, self.scope, self.table),
+            Bound::Unbounded => self.key.begin(self.code, self.scope, self.table),
+        };
+        let stop = match range.end_bound() {
+            Bound::Included(key) => key.upper_bound(self.code, self.scope, self.table).0,
+            Bound::Excluded(key) => key.lower_bound(self.code, self.scope, self.table).0,
+            Bound::Unbounded => end,
+        };
+        self.iterator(start, stop)
+    }
+
+    /// The rows from the highest secondary key to the lowest
+    pub fn rev_iter(&self) -> Rev<SecondaryTableIterator<'_, K, T>> {
+        self.iter().rev()
+    }
+
+    /// Reads every row in index order
+    pub fn iter_rows(&self) -> TableRows<SecondaryTableIterator<'_, K, T>, T> {
+        self.iter().rows()
+    }
+
+    /// The row with the lowest secondary key
+    pub fn first(&self) -> Option<SecondaryTableCursor<'_, K, T>> {
+        self.cursor(self.key.begin(self.code, self.scope, self.table))
+    }
+
+    /// The row with the highest secondary key
+    pub fn last(&self) -> Option<SecondaryTableCursor<'_, K, T>> {
+        // `previous` aborts on the -1 end of a table that doesn't exist yet
+        let end = self.end();
+        if end == -1 {
+            return None;
+        }
+        let (value, pk) = self.key.previous(end);
+        if value < 0 {
+            None
+        } else {
+            self.cursor((value, pk))
+        }
     }
 }
 
@@ -501,17 +825,7 @@ where
         N: Into<K>,
     {
         let key = key.into();
-        let (value, pk) = key.lower_bound(self.code, self.scope, self.table);
-        let end = key.end(self.code, self.scope, self.table);
-        if value != end {
-            Some(SecondaryTableCursor {
-                value,
-                pk,
-                index: self,
-            })
-        } else {
-            None
-        }
+        self.cursor(key.lower_bound(self.code, self.scope, self.table))
     }
 
     fn upper_bound<N>(&'a self, key: N) -> Option<Self::Cursor>
@@ -519,17 +833,7 @@ where
         N: Into<K>,
     {
         let key = key.into();
-        let (value, pk) = key.upper_bound(self.code, self.scope, self.table);
-        let end = key.end(self.code, self.scope, self.table);
-        if value != end {
-            Some(SecondaryTableCursor {
-                value,
-                pk,
-                index: self,
-            })
-        } else {
-            None
-        }
+        self.cursor(key.upper_bound(self.code, self.scope, self.table))
     }
 
     fn emplace(&self, payer: AccountName, item: &T) -> Result<(), WriteError> {
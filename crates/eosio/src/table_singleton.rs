@@ -0,0 +1,121 @@
+use crate::account::AccountName;
+use crate::bytes::{ReadError, WriteError};
+use crate::lib::PhantomData;
+use crate::table::*;
+use eosio_sys::ctypes::*;
+
+/// A table with at most one row per scope, e.g. for contract configuration.
+/// The row is stored with the table name as its primary key, which matches
+/// `eosio::singleton` in the C++ SDK.
+#[derive(Copy, Clone, Debug)]
+pub struct Singleton<T>
+where
+    T: SingletonRow,
+{
+    code: AccountName,
+    scope: ScopeName,
+    name: TableName,
+    _data: PhantomData<T>,
+}
+
+impl<T> Singleton<T>
+where
+    T: SingletonRow,
+{
+    pub fn new<C, S, N>(code: C, scope: S, name: N) -> Self
+    where
+        C: Into<AccountName>,
+        S: Into<ScopeName>,
+        N: Into<TableName>,
+    {
+        Singleton {
+            code: code.into(),
+            scope: scope.into(),
+            name: name.into(),
+            _data: PhantomData,
+        }
+    }
+
+    /// The iterator of the row, if it exists
+    fn find(&self) -> Option<i32> {
+        let itr = unsafe {
+            ::eosio_sys::db_find_i64(
+                self.code.into(),
+                self.scope.into(),
+                self.name.into(),
+                self.name.into(),
+            )
+        };
+        let end = unsafe {
+            ::eosio_sys::db_end_i64(self.code.into(), self.scope.into(), self.name.into())
+        };
+        if itr == end {
+            None
+        } else {
+            Some(itr)
+        }
+    }
+
+    pub fn exists(&self) -> bool {
+        self.find().is_some()
+    }
+
+    /// Reads the row. Returns `Ok(None)` if it hasn't been set.
+    pub fn get(&self) -> Result<Option<T>, ReadError> {
+        let itr = match self.find() {
+            Some(itr) => itr,
+            None => return Ok(None),
+        };
+        let nullptr: *mut c_void = ::std::ptr::null_mut() as *mut _ as *mut c_void;
+        let size = unsafe { ::eosio_sys::db_get_i64(itr, nullptr, 0) };
+        let mut bytes = vec![0u8; size as usize];
+        let ptr: *mut c_void = &mut bytes[..] as *mut _ as *mut c_void;
+        unsafe {
+            ::eosio_sys::db_get_i64(itr, ptr, size as u32);
+        }
+        T::read(&bytes, 0).map(|(t, _)| Some(t))
+    }
+
+    /// Reads the row, or returns `T::default()` if it hasn't been set
+    pub fn get_or_default(&self) -> Result<T, ReadError>
+    where
+        T: Default,
+    {
+        self.get().map(Option::unwrap_or_default)
+    }
+
+    /// Creates or replaces the row
+    pub fn set(&self, payer: AccountName, value: &T) -> Result<(), WriteError> {
+        let size = value.num_bytes();
+        let mut bytes = vec![0u8; size];
+        let pos = value.write(&mut bytes, 0)?;
+        let ptr: *const c_void = &bytes[..] as *const _ as *const c_void;
+        match self.find() {
+            Some(itr) => unsafe { ::eosio_sys::db_update_i64(itr, payer.into(), ptr, pos as u32) },
+            None => {
+                unsafe {
+                    ::eosio_sys::db_store_i64(
+                        self.scope.into(),
+                        self.name.into(),
+                        payer.into(),
+                        self.name.into(),
+                        ptr,
+                        pos as u32,
+                    )
+                };
+            }
+        }
+        Ok(())
+    }
+
+    /// Removes the row. Returns `false` if it didn't exist.
+    pub fn remove(&self) -> bool {
+        match self.find() {
+            Some(itr) => {
+                unsafe { ::eosio_sys::db_remove_i64(itr) };
+                true
+            }
+            None => false,
+        }
+    }
+}
@@ -1,5 +1,6 @@
 #[cfg(feature = "contract")]
 use crate::assert::*;
+use crate::lib::fmt;
 use eosio_macros::*;
 
 #[derive(
@@ -117,6 +118,12 @@ impl Time {
     }
 }
 
+impl fmt::Display for Time {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Time({})", self.0)
+    }
+}
+
 #[cfg(feature = "contract")]
 impl crate::print::Print for Time {
     fn print(&self) {
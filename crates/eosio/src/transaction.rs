@@ -0,0 +1,147 @@
+#[cfg(feature = "contract")]
+use crate::account::AccountName;
+#[cfg(feature = "contract")]
+use crate::action::ActionId;
+use crate::action::{Action, ContextFreeAction, ToAction};
+use crate::bytes::{NumBytes, Read, ReadError, UnsignedInt, Write, WriteError};
+#[cfg(feature = "contract")]
+use crate::time::Time;
+use eosio_macros::*;
+
+/// Action data that hasn't been deserialized
+#[derive(Debug, PartialEq, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct PackedData(pub Vec<u8>);
+
+impl PackedData {
+    pub fn unpack<T>(&self) -> Result<T, ReadError>
+    where
+        T: Read,
+    {
+        T::read(&self.0, 0).map(|(value, _)| value)
+    }
+}
+
+impl Read for PackedData {
+    fn read(bytes: &[u8], pos: usize) -> Result<(Self, usize), ReadError> {
+        match bytes.get(pos..) {
+            Some(rest) => Ok((PackedData(rest.to_vec()), bytes.len())),
+            None => Err(ReadError::NotEnoughBytes),
+        }
+    }
+}
+
+impl Write for PackedData {
+    fn write(&self, bytes: &mut [u8], pos: usize) -> Result<usize, WriteError> {
+        let end_pos = pos + self.0.len();
+        match bytes.get_mut(pos..end_pos) {
+            Some(slice) => {
+                slice.copy_from_slice(&self.0);
+                Ok(end_pos)
+            }
+            None => Err(WriteError::NotEnoughSpace),
+        }
+    }
+}
+
+impl NumBytes for PackedData {
+    fn num_bytes(&self) -> usize {
+        self.0.len()
+    }
+}
+
+#[derive(Read, Write, NumBytes, Debug, PartialEq, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct TransactionHeader {
+    /// Seconds since the Unix epoch
+    pub expiration: u32,
+    pub ref_block_num: u16,
+    pub ref_block_prefix: u32,
+    pub max_net_usage_words: UnsignedInt,
+    pub max_cpu_usage_ms: u8,
+    pub delay_sec: UnsignedInt,
+}
+
+#[derive(Read, Write, NumBytes, Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct Transaction {
+    pub header: TransactionHeader,
+    pub context_free_actions: Vec<ContextFreeAction<PackedData>>,
+    pub actions: Vec<Action<PackedData>>,
+    pub transaction_extensions: Vec<(u16, Vec<u8>)>,
+}
+
+#[cfg(feature = "contract")]
+impl Transaction {
+    /// An empty transaction that runs after `delay_sec` seconds
+    pub fn deferred(delay_sec: u32) -> Self {
+        Transaction {
+            header: TransactionHeader {
+                expiration: Time::now().seconds() + 60,
+                delay_sec: delay_sec.into(),
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    /// Schedules the transaction. If `replace_existing` is `false`, this fails
+    /// when a transaction sent with the same `id` is still pending.
+    pub fn send_deferred<P>(
+        &self,
+        id: ActionId,
+        payer: P,
+        replace_existing: bool,
+    ) -> Result<(), WriteError>
+    where
+        P: Into<AccountName>,
+    {
+        let mut bytes = vec![0u8; self.num_bytes()];
+        let pos = self.write(&mut bytes, 0)?;
+        let sender_id = sender_id(id);
+        let payer: AccountName = payer.into();
+        unsafe {
+            ::eosio_sys::send_deferred(
+                &sender_id,
+                payer.into(),
+                bytes.as_ptr(),
+                pos,
+                replace_existing.into(),
+            )
+        }
+        Ok(())
+    }
+
+    /// Cancels a deferred transaction. Returns `false` if it wasn't found.
+    pub fn cancel_deferred(id: ActionId) -> bool {
+        let sender_id = sender_id(id);
+        unsafe { ::eosio_sys::cancel_deferred(&sender_id) == 1 }
+    }
+}
+
+#[cfg(feature = "contract")]
+fn sender_id(id: ActionId) -> ::eosio_sys::uint128_t {
+    [id.0 as u64, (id.0 >> 64) as u64]
+}
+
+/// The payload of the `onerror` action, sent by `eosio` to the sender of a
+/// deferred transaction that failed.
+#[derive(Read, Write, NumBytes, Debug, PartialEq, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct OnError {
+    /// The ID the deferred transaction was sent with
+    pub sender_id: u128,
+    /// The packed transaction that failed
+    pub sent_trx: Vec<u8>,
+}
+
+impl OnError {
+    /// Decodes the failed transaction, e.g. to retry or refund its actions
+    pub fn unpack_sent_trx(&self) -> Result<Transaction, ReadError> {
+        Transaction::read(&self.sent_trx, 0).map(|(trx, _)| trx)
+    }
+}
+
+impl ToAction for OnError {
+    const NAME: u64 = n!(onerror);
+}
@@ -57,6 +57,12 @@ test_type!(
     test_time, Time, Time::zero()
     test_f32, f32, -0.12345
     test_f64, f64, -0.12345
+    test_public_key, PublicKey, PublicKey::from([1u8; 34])
+    test_u128, u128, 1
+    test_i128, i128, -1
+    test_unsigned_int, UnsignedInt, UnsignedInt(300)
+    test_unsigned_int_max, UnsignedInt, UnsignedInt(u32::max_value())
+    test_producer_key, ProducerKey, ProducerKey { producer_name: AccountName::from(n!(test)), block_signing_key: PublicKey::from([2u8; 34]) }
 );
 
 #[test]
@@ -103,6 +109,66 @@ fn test_struct_unnamed_fields() {
     assert_eq!(thing1.2, 3);
 }
 
+#[test]
+fn test_unsigned_int_bytes() {
+    let mut bytes = [0u8; 5];
+    assert_eq!(UnsignedInt(127).write(&mut bytes, 0).unwrap(), 1);
+    assert_eq!(bytes[0], 0x7f);
+    assert_eq!(UnsignedInt(128).write(&mut bytes, 0).unwrap(), 2);
+    assert_eq!(&bytes[..2], &[0x80, 0x01]);
+    assert_eq!(UnsignedInt(128).num_bytes(), 2);
+}
+
+#[test]
+fn test_long_vec() {
+    let thing: Vec<u8> = vec![1; 300];
+    let mut bytes = vec![0u8; thing.num_bytes()];
+    assert_eq!(thing.write(&mut bytes, 0).unwrap(), 302);
+    let (result, pos) = Vec::<u8>::read(&bytes, 0).unwrap();
+    assert_eq!(thing, result);
+    assert_eq!(pos, 302);
+}
+
+#[test]
+fn test_on_error() {
+    let action = Action {
+        account: AccountName::from(n!(eosio.token)),
+        name: ActionName::from(n!(transfer)),
+        authorization: vec![Authorization::active(AccountName::from(n!(alice)))],
+        data: PackedData(vec![1, 2, 3, 4]),
+    };
+    let trx = Transaction {
+        header: TransactionHeader {
+            expiration: 1,
+            delay_sec: UnsignedInt(200),
+            ..Default::default()
+        },
+        context_free_actions: vec![],
+        actions: vec![action],
+        transaction_extensions: vec![],
+    };
+    let mut sent_trx = vec![0u8; trx.num_bytes()];
+    trx.write(&mut sent_trx, 0).unwrap();
+
+    let error = OnError {
+        sender_id: 1 << 100,
+        sent_trx,
+    };
+    let mut bytes = vec![0u8; error.num_bytes()];
+    error.write(&mut bytes, 0).unwrap();
+    let (result, _) = OnError::read(&bytes, 0).unwrap();
+    assert_eq!(error, result);
+
+    let result = result.unpack_sent_trx().unwrap();
+    assert_eq!(result.header, trx.header);
+    assert_eq!(result.actions.len(), 1);
+    assert_eq!(
+        result.actions[0].authorization,
+        trx.actions[0].authorization
+    );
+    assert_eq!(result.actions[0].data, PackedData(vec![1, 2, 3, 4]));
+}
+
 #[test]
 fn test_read_pos() {
     let bytes = &[
@@ -152,3 +218,121 @@ fn test_write_pos() {
     let pos = 1u64.write(bytes, pos).unwrap();
     assert_eq!(pos, 15);
 }
+
+#[test]
+fn test_context_free_action() {
+    let action = ContextFreeAction {
+        account: AccountName::from(n!(eosio.token)),
+        name: ActionName::from(n!(verify)),
+        data: 5u32,
+    };
+    let mut bytes = vec![0u8; action.num_bytes()];
+    action.write(&mut bytes, 0).unwrap();
+
+    let (result, pos) = Action::<u32>::read(&bytes, 0).unwrap();
+    assert_eq!(pos, bytes.len());
+    assert!(result.authorization.is_empty());
+    assert_eq!(result.data, 5);
+
+    let action = Action {
+        authorization: vec![Authorization::active(AccountName::from(n!(alice)))],
+        ..result
+    };
+    let mut bytes = vec![0u8; action.num_bytes()];
+    action.write(&mut bytes, 0).unwrap();
+    assert!(ContextFreeAction::<u32>::read(&bytes, 0).is_err());
+}
+
+#[test]
+fn test_float128_from_f64() {
+    assert_eq!(Float128::from(0.0), Float128(0));
+    assert_eq!(Float128::from(-0.0), Float128(1 << 127));
+    assert_eq!(Float128::from(1.0), Float128(0x3fff << 112));
+    assert_eq!(Float128::from(-2.5), Float128((0xc000 << 112) | (1 << 110)));
+    assert_eq!(
+        Float128::from(1.5f32),
+        Float128((0x3fff << 112) | (1 << 111))
+    );
+    assert_eq!(
+        Float128::from(::std::f64::INFINITY),
+        Float128(0x7fff << 112)
+    );
+    // The smallest subnormal f64, 2^-1074
+    assert_eq!(Float128::from(5e-324), Float128((16383 - 1074) << 112));
+    assert_eq!(
+        Float128::from(3.0 * 5e-324),
+        Float128(((16383 - 1073) << 112) | (1 << 111))
+    );
+}
+
+#[test]
+fn test_float128_order() {
+    let values = [
+        ::std::f64::NEG_INFINITY,
+        -2.5,
+        -1.0,
+        -5e-324,
+        -0.0,
+        0.0,
+        5e-324,
+        1.0,
+        2.5,
+    ];
+    for pair in values.windows(2) {
+        assert!(Float128::from(pair[0]) < Float128::from(pair[1]));
+    }
+}
+
+#[derive(Read, Write, NumBytes)]
+struct RowV1 {
+    id: u64,
+    amount: u32,
+}
+
+#[eosio_table(rows, version = 2)]
+struct Row {
+    #[primary]
+    id: u64,
+    amount: u64,
+    memo: String,
+}
+
+impl Row {
+    fn migrate_from_v1(old: RowV1) -> Self {
+        Row {
+            id: old.id,
+            amount: u64::from(old.amount),
+            memo: String::new(),
+        }
+    }
+}
+
+#[test]
+fn test_versioned_row() {
+    let row = Row {
+        id: 1,
+        amount: 2,
+        memo: "hi".to_string(),
+    };
+    let mut bytes = [0u8; 100];
+    let pos = row.write(&mut bytes, 0).unwrap();
+    assert_eq!(bytes[0], 2);
+    assert_eq!(pos, row.num_bytes());
+    assert_eq!(Row::read(&bytes, 0).unwrap(), (row, pos));
+
+    let old = RowV1 { id: 3, amount: 4 };
+    let pos = 1u8.write(&mut bytes, 0).unwrap();
+    let pos = old.write(&mut bytes, pos).unwrap();
+    let migrated = Row {
+        id: 3,
+        amount: 4,
+        memo: String::new(),
+    };
+    assert_eq!(Row::read(&bytes, 0).unwrap(), (migrated, pos));
+
+    bytes[0] = 3;
+    match Row::read(&bytes, 0) {
+        Err(ReadError::UnknownVersion(3)) => (),
+        _ => panic!("expected an unknown version to be rejected"),
+    }
+}
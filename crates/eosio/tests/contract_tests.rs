@@ -0,0 +1,58 @@
+use eosio::*;
+
+#[eosio_contract]
+struct Greeter {
+    #[init(receiver.to_string())]
+    owner: String,
+    count: u64,
+}
+
+#[eosio_contract]
+impl Greeter {
+    pub fn greet(&self, name: AccountName) -> String {
+        format!("{} greets {} ({})", self.owner, name, self.next_count())
+    }
+
+    fn next_count(&self) -> u64 {
+        self.count + 1
+    }
+}
+
+#[test]
+fn test_contract_off_chain() {
+    let contract = Greeter::new(n!(alice).into(), n!(bob).into());
+    assert_eq!(contract.receiver, AccountName::from(n!(alice)));
+    assert_eq!(contract.code, AccountName::from(n!(bob)));
+    assert_eq!(contract.greet(n!(dave).into()), "alice greets dave (1)");
+}
+
+#[eosio_action(name = "double.it", off_chain)]
+fn double(value: u64) -> u64 {
+    value * 2
+}
+
+#[test]
+fn test_action_off_chain() {
+    assert_eq!(double(21), 42);
+    assert_eq!(<DoubleAction as ToAction>::NAME, n!(double.it));
+}
+
+struct MathClient(AccountName);
+
+impl Client for MathClient {
+    fn account(&self) -> AccountName {
+        self.0
+    }
+}
+
+#[test]
+fn test_action_client() {
+    let auth = Authorization {
+        actor: n!(alice).into(),
+        permission: n!(active).into(),
+    };
+    let action = MathClient(n!(math).into()).double(auth, 21);
+    assert_eq!(action.account, AccountName::from(n!(math)));
+    assert_eq!(action.name, ActionName::from(n!(double.it)));
+    assert_eq!(action.data.value, 21);
+}
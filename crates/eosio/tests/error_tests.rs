@@ -0,0 +1,47 @@
+use eosio::*;
+
+#[derive(ContractError, Debug)]
+#[allow(dead_code)]
+enum TokenError {
+    #[error(code = 1, message = "insufficient balance")]
+    InsufficientBalance { balance: u64 },
+    #[error(code = 2)]
+    SymbolMismatch(u64, u64),
+}
+
+#[derive(ContractError, Debug)]
+enum GameError {
+    NotYourTurn = 10,
+    #[error(message = "game is over")]
+    GameOver = 11,
+}
+
+#[test]
+fn test_contract_error() {
+    let error = TokenError::InsufficientBalance { balance: 1 };
+    assert_eq!(error.code(), 1);
+    assert_eq!(error.message(), "insufficient balance");
+
+    let error = TokenError::SymbolMismatch(1, 2);
+    assert_eq!(error.code(), 2);
+    assert_eq!(error.message(), "SymbolMismatch");
+}
+
+#[test]
+fn test_contract_error_discriminants() {
+    assert_eq!(GameError::NotYourTurn.code(), 10);
+    assert_eq!(GameError::GameOver.code(), 11);
+    assert_eq!(GameError::GameOver.message(), "game is over");
+}
+
+#[test]
+fn test_error_messages() {
+    assert_eq!(
+        TokenError::ERROR_MESSAGES,
+        &[(1, "insufficient balance"), (2, "SymbolMismatch")]
+    );
+    assert_eq!(
+        GameError::ERROR_MESSAGES,
+        &[(10, "NotYourTurn"), (11, "game is over")]
+    );
+}
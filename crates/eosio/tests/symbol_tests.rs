@@ -9,3 +9,29 @@ fn basic_symbol_tests() {
     let num: u64 = name.into();
     assert_eq!(num, 1_413_891_924);
 }
+
+#[test]
+fn symbol_display() {
+    let symbol = Symbol::from(s!(4, EOS));
+    assert_eq!(symbol.to_string(), "4,EOS");
+    assert_eq!(symbol.name().to_string(), "EOS");
+    assert_eq!(format!("{:>5}", symbol.name()), "  EOS");
+
+    let asset = Asset {
+        amount: 10_000,
+        symbol,
+    };
+    assert_eq!(asset.to_string(), "1.0000 EOS");
+}
+
+#[test]
+fn eosio_format_returns_string() {
+    let auth = Authorization::active(n!(alice).into());
+    let asset = Asset {
+        amount: 100,
+        symbol: Symbol::from(s!(2, EOS)),
+    };
+    let s = eosio_format!("{} has {}", auth, asset);
+    assert_eq!(s, "alice@active has 1.00 EOS");
+    assert_eq!(eosio_format!("{}", Sha1::default()), "0".repeat(40));
+}
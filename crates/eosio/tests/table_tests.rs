@@ -0,0 +1,43 @@
+use eosio::*;
+
+#[eosio_table(offers)]
+struct Offer {
+    #[primary]
+    id: u64,
+    #[secondary]
+    owner: AccountName,
+    #[secondary]
+    price: f64,
+    #[secondary]
+    hash: Sha256,
+    memo: String,
+}
+
+fn offer(memo: &str) -> Offer {
+    Offer {
+        id: 1,
+        owner: n!(alice).into(),
+        price: 1.5,
+        hash: Sha256::default(),
+        memo: memo.to_string(),
+    }
+}
+
+#[test]
+fn test_billable_size() {
+    let row = offer("");
+    let keys = 128 + 128 + 152;
+    assert_eq!(row.billable_size(), ROW_OVERHEAD + row.num_bytes() + keys);
+    assert_eq!(SecondaryKey::from(1u128).billable_size(), 136);
+}
+
+#[test]
+fn test_estimate_ram_delta() {
+    let short = offer("");
+    let long = offer("hello");
+    let size = short.billable_size() as i64;
+    assert_eq!(Offer::estimate_ram_delta(None, Some(&short)), size);
+    assert_eq!(Offer::estimate_ram_delta(Some(&short), None), -size);
+    assert_eq!(Offer::estimate_ram_delta(Some(&short), Some(&long)), 5);
+    assert_eq!(Offer::estimate_ram_delta(None, None), 0);
+}
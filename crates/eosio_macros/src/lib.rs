@@ -6,6 +6,12 @@ pub use eosio_macros_impl::eosio_abi;
 #[proc_macro_hack(support_nested)]
 pub use eosio_macros_impl::eosio_print;
 
+#[proc_macro_hack(support_nested)]
+pub use eosio_macros_impl::eosio_format;
+
+#[proc_macro_hack(support_nested)]
+pub use eosio_macros_impl::eosio_println;
+
 #[proc_macro_hack]
 pub use eosio_macros_impl::n;
 
@@ -13,5 +19,6 @@ pub use eosio_macros_impl::n;
 pub use eosio_macros_impl::s;
 
 pub use eosio_macros_impl::{
-    eosio_action, eosio_name, eosio_table, NumBytes, Read, TableRow, Write,
+    eosio_action, eosio_contract, eosio_name, eosio_table, ContractError, NumBytes, Print, Read,
+    TableRow, Write,
 };
@@ -0,0 +1,118 @@
+use crate::proc_macro::TokenStream;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Expr, Fields, Lit, Meta, NestedMeta};
+
+/// Codes at or above this are reserved for the system contracts
+const RESERVED_CODES: u64 = 5_000_000_000_000_000_000;
+
+pub fn expand(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let eosio = crate::paths::eosio();
+
+    let name = input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let data = match input.data {
+        Data::Enum(data) => data,
+        _ => panic!("ContractError can only be derived for enums"),
+    };
+
+    let mut codes = Vec::new();
+    let mut messages = Vec::new();
+    let mut patterns = Vec::new();
+    for variant in data.variants.iter() {
+        let ident = &variant.ident;
+        let mut code = None;
+        let mut message = None;
+        for attr in variant.attrs.iter() {
+            let meta = match attr.interpret_meta() {
+                Some(Meta::List(ref list)) if list.ident == "error" => list.clone(),
+                _ => continue,
+            };
+            for nested in meta.nested.iter() {
+                match nested {
+                    NestedMeta::Meta(Meta::NameValue(value)) if value.ident == "code" => {
+                        match value.lit {
+                            Lit::Int(ref lit) => code = Some(lit.value()),
+                            _ => panic!("#[error(code = ...)] must be an integer"),
+                        }
+                    }
+                    NestedMeta::Meta(Meta::NameValue(value)) if value.ident == "message" => {
+                        match value.lit {
+                            Lit::Str(ref lit) => message = Some(lit.value()),
+                            _ => panic!("#[error(message = ...)] must be a string"),
+                        }
+                    }
+                    _ => panic!("expected #[error(code = 1, message = \"...\")]"),
+                }
+            }
+        }
+
+        if code.is_none() {
+            if let Some((_, Expr::Lit(ref expr))) = variant.discriminant {
+                if let Lit::Int(ref lit) = expr.lit {
+                    code = Some(lit.value());
+                }
+            }
+        }
+        let code = match code {
+            Some(code) => code,
+            None => panic!(
+                "variant `{}` needs a stable code: add #[error(code = ...)] or a discriminant",
+                ident
+            ),
+        };
+        if code >= RESERVED_CODES {
+            panic!(
+                "variant `{}` uses code {}, but codes from {} are reserved",
+                ident, code, RESERVED_CODES
+            );
+        }
+        if codes.contains(&code) {
+            panic!("variant `{}` reuses code {}", ident, code);
+        }
+
+        let pattern = match variant.fields {
+            Fields::Named(_) => quote!(#name::#ident { .. }),
+            Fields::Unnamed(_) => quote!(#name::#ident(..)),
+            Fields::Unit => quote!(#name::#ident),
+        };
+        codes.push(code);
+        messages.push(message.unwrap_or_else(|| ident.to_string()));
+        patterns.push(pattern);
+    }
+
+    let code_arms = patterns
+        .iter()
+        .zip(codes.iter())
+        .map(|(pattern, code)| quote!(#pattern => #code,));
+    let message_arms = patterns
+        .iter()
+        .zip(messages.iter())
+        .map(|(pattern, message)| quote!(#pattern => #message,));
+    let entries = codes
+        .iter()
+        .zip(messages.iter())
+        .map(|(code, message)| quote!((#code, #message),));
+
+    let expanded = quote! {
+        #[automatically_derived]
+        impl #impl_generics #eosio::ContractError for #name #ty_generics #where_clause {
+            const ERROR_MESSAGES: &'static [(u64, &'static str)] = &[#(#entries)*];
+
+            fn code(&self) -> u64 {
+                match self {
+                    #(#code_arms)*
+                }
+            }
+
+            fn message(&self) -> &'static str {
+                match self {
+                    #(#message_arms)*
+                }
+            }
+        }
+    };
+
+    TokenStream::from(expanded)
+}
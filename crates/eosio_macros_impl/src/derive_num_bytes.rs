@@ -56,6 +56,14 @@ pub fn expand(input: TokenStream) -> TokenStream {
         Data::Enum(_) | Data::Union(_) => unimplemented!(),
     };
 
+    let add_to_count = match crate::derive_table_row::table_version(&input.attrs) {
+        Some(_) => quote! {
+            count += 1;
+            #add_to_count
+        },
+        None => add_to_count,
+    };
+
     let expanded = quote! {
         #[automatically_derived]
         impl #impl_generics #eosio::NumBytes for #name #ty_generics #where_clause {
@@ -1 +1,157 @@
+use crate::proc_macro::TokenStream;
+#[cfg(feature = "contract")]
+use proc_macro2::TokenStream as TokenStream2;
+#[cfg(feature = "contract")]
+use quote::quote;
+#[cfg(feature = "contract")]
+use syn::{parse_macro_input, parse_quote, Data, DeriveInput, Fields, GenericParam, Ident, Index};
 
+#[cfg(not(feature = "contract"))]
+pub fn expand(_input: TokenStream) -> TokenStream {
+    TokenStream::new()
+}
+
+#[cfg(feature = "contract")]
+pub fn expand(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let eosio = crate::paths::eosio();
+
+    let name = input.ident;
+
+    let mut generics = input.generics;
+    for param in &mut generics.params {
+        if let GenericParam::Type(ref mut type_param) = *param {
+            type_param.bounds.push(parse_quote!(#eosio::Print));
+        }
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let call_site = ::proc_macro2::Span::call_site();
+    let prints = match input.data {
+        Data::Struct(ref data) => {
+            let accessors: Vec<TokenStream2> = match data.fields {
+                Fields::Named(ref fields) => fields
+                    .named
+                    .iter()
+                    .map(|f| {
+                        let ident = &f.ident;
+                        quote!(&self.#ident)
+                    })
+                    .collect(),
+                Fields::Unnamed(ref fields) => (0..fields.unnamed.len())
+                    .map(|i| {
+                        let index = Index {
+                            index: i as u32,
+                            span: call_site,
+                        };
+                        quote!(&self.#index)
+                    })
+                    .collect(),
+                Fields::Unit => Vec::new(),
+            };
+            print_fields(&name.to_string(), &data.fields, &accessors)
+        }
+        Data::Enum(ref data) => {
+            let arms = data.variants.iter().map(|variant| {
+                let ident = &variant.ident;
+                let bindings: Vec<Ident> = match variant.fields {
+                    Fields::Named(ref fields) => fields
+                        .named
+                        .iter()
+                        .map(|f| f.ident.clone().unwrap())
+                        .collect(),
+                    Fields::Unnamed(ref fields) => (0..fields.unnamed.len())
+                        .map(|i| Ident::new(format!("field_{}", i).as_str(), call_site))
+                        .collect(),
+                    Fields::Unit => Vec::new(),
+                };
+                let accessors: Vec<TokenStream2> = bindings.iter().map(|b| quote!(#b)).collect();
+                let pattern = match variant.fields {
+                    Fields::Named(_) => quote!(#name::#ident { #(#bindings),* }),
+                    Fields::Unnamed(_) => quote!(#name::#ident(#(#bindings),*)),
+                    Fields::Unit => quote!(#name::#ident),
+                };
+                let prints = print_fields(&ident.to_string(), &variant.fields, &accessors);
+                quote! {
+                    #pattern => {
+                        #prints
+                    }
+                }
+            });
+            quote! {
+                match self {
+                    #(#arms)*
+                }
+            }
+        }
+        Data::Union(_) => unimplemented!(),
+    };
+
+    let expanded = quote! {
+        #[automatically_derived]
+        impl #impl_generics #eosio::Print for #name #ty_generics #where_clause {
+            fn print(&self) {
+                #prints
+            }
+        }
+    };
+
+    TokenStream::from(expanded)
+}
+
+/// Prints `Name { a: .., b: .. }`, `Name(.., ..)` or `Name`, with each field
+/// printed through the expression in `accessors`.
+#[cfg(feature = "contract")]
+fn print_fields(name: &str, fields: &Fields, accessors: &[TokenStream2]) -> TokenStream2 {
+    let eosio = crate::paths::eosio();
+    match fields {
+        Fields::Named(ref fields) => {
+            let prints = fields
+                .named
+                .iter()
+                .zip(accessors)
+                .enumerate()
+                .map(|(i, (f, access))| {
+                    let ident = f.ident.as_ref().unwrap();
+                    let label = if i == 0 {
+                        format!(" {}: ", ident)
+                    } else {
+                        format!(", {}: ", ident)
+                    };
+                    quote! {
+                        #eosio::Print::print(&#label);
+                        #eosio::Print::print(#access);
+                    }
+                });
+            let open = format!("{} {{", name);
+            let close = if accessors.is_empty() { "}" } else { " }" };
+            quote! {
+                #eosio::Print::print(&#open);
+                #(#prints)*
+                #eosio::Print::print(&#close);
+            }
+        }
+        Fields::Unnamed(_) => {
+            let prints = accessors.iter().enumerate().map(|(i, access)| {
+                let separator = if i == 0 {
+                    quote!()
+                } else {
+                    quote!(#eosio::Print::print(&", ");)
+                };
+                quote! {
+                    #separator
+                    #eosio::Print::print(#access);
+                }
+            });
+            let open = format!("{}(", name);
+            quote! {
+                #eosio::Print::print(&#open);
+                #(#prints)*
+                #eosio::Print::print(&")");
+            }
+        }
+        Fields::Unit => quote! {
+            #eosio::Print::print(&#name);
+        },
+    }
+}
@@ -72,6 +72,31 @@ pub fn expand(input: TokenStream) -> TokenStream {
         Data::Enum(_) | Data::Union(_) => unimplemented!(),
     };
 
+    // Versioned rows start with their version, and older versions are read
+    // with the `migrate_from_vN` functions that the contract provides
+    let reads = match crate::derive_table_row::table_version(&input.attrs) {
+        Some(version) => {
+            let migrations = (1..version).map(|old_version| {
+                let migrate = Ident::new(&format!("migrate_from_v{}", old_version), call_site);
+                quote! {
+                    #old_version => {
+                        let (old, pos) = #eosio::Read::read(bytes, pos)?;
+                        Ok((Self::#migrate(old), pos))
+                    }
+                }
+            });
+            quote! {
+                let (version, pos) = <u8 as #eosio::Read>::read(bytes, pos)?;
+                match version {
+                    #version => { #reads }
+                    #(#migrations)*
+                    version => Err(#eosio::ReadError::UnknownVersion(version)),
+                }
+            }
+        }
+        None => reads,
+    };
+
     let expanded = quote! {
         #[automatically_derived]
         impl #impl_generics #eosio::Read for #name #ty_generics #where_clause {
@@ -1,9 +1,12 @@
 use crate::proc_macro::TokenStream;
-use proc_macro2::{Ident, Span};
+use eosio_sys::string_to_name;
+use proc_macro2::{Ident, Literal, TokenStream as TokenStream2};
 use quote::quote;
-use syn::{parse_macro_input, parse_quote, Data, DeriveInput, Fields, GenericParam, Meta};
+use syn::{
+    parse_macro_input, parse_quote, Attribute, Data, DeriveInput, Expr, Fields, GenericParam, Lit,
+    Meta, MetaList, MetaNameValue, NestedMeta, Type,
+};
 
-#[cfg(not(feature = "contract"))]
 pub fn expand(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
     let eosio = crate::paths::eosio();
@@ -18,121 +21,19 @@ pub fn expand(input: TokenStream) -> TokenStream {
     }
     let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
-    let table_name = input.attrs.iter().fold(None, |acc, attr| {
-        match attr.interpret_meta() {
-            Some(meta) => {
-                let name = meta.name();
-                if name == "table_name" {
-                    if acc.is_some() {
-                        panic!("only 1 table_name attribute allowed per struct");
-                    }
-                    match meta {
-                        Meta::NameValue(meta) => {
-                            let lit = meta.lit;
-                            let s = Ident::new(format!("{}", quote!(#lit)).as_str().trim_matches('"'), Span::call_site());
-                            Some(s)
-                        }
-                        _ => {
-                            panic!("invalid table_name attribute. must be in the form #[table_name = \"test\"]");
-                        }
-                    }
-                } else {
-                    acc
-                }
-            }
-            None => acc,
-        }
-    });
-
-    if table_name.is_none() {
-        panic!("#[table_name] attribute must be used when deriving from TableRow");
-    }
+    let table_name = table_name(&input.attrs);
+    let scope = table_scope(&input.attrs);
 
-    let expanded = match input.data {
-        Data::Struct(ref data) => match data.fields {
-            Fields::Named(ref fields) => {
-                let mut primary_key = None;
-                for field in fields.named.iter() {
-                    for attr in field.attrs.iter() {
-                        let name = attr.interpret_meta().map(|m| m.name());
-                        let is_primary = name.map(|n| n == "primary").unwrap_or_else(|| false);
-                        if is_primary {
-                            if primary_key.is_none() {
-                                primary_key = field.ident.clone();
-                            } else {
-                                panic!("only 1 primary key allowed");
-                            }
-                        }
-                    }
-                }
-                if primary_key.is_none() {
-                    panic!("no primary key found");
-                }
-
-                quote! {
-                    #[automatically_derived]
-                    impl #impl_generics #eosio::TableRow for #name #ty_generics #where_clause {
-                        const TABLE_NAME: u64 = #eosio::n!(#table_name);
-
-                        fn primary_key(&self) -> u64 {
-                            self.#primary_key.into()
-                        }
-                    }
-                }
+    let versioned = match table_version(&input.attrs) {
+        Some(version) => quote! {
+            #[automatically_derived]
+            impl #impl_generics #eosio::VersionedRow for #name #ty_generics #where_clause {
+                const VERSION: u8 = #version;
             }
-            _ => unimplemented!(),
         },
-        _ => unimplemented!(),
+        None => quote!(),
     };
 
-    TokenStream::from(expanded)
-}
-
-#[cfg(feature = "contract")]
-pub fn expand(input: TokenStream) -> TokenStream {
-    let input = parse_macro_input!(input as DeriveInput);
-    let eosio = crate::paths::eosio();
-
-    let name = input.ident.clone();
-
-    let mut generics = input.generics.clone();
-    for param in &mut generics.params {
-        if let GenericParam::Type(ref mut type_param) = *param {
-            type_param.bounds.push(parse_quote!(#eosio::Read));
-        }
-    }
-    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
-
-    let table_name = input.attrs.iter().fold(None, |acc, attr| {
-        match attr.interpret_meta() {
-            Some(meta) => {
-                let name = meta.name();
-                if name == "table_name" {
-                    if acc.is_some() {
-                        panic!("only 1 table_name attribute allowed per struct");
-                    }
-                    match meta {
-                        Meta::NameValue(meta) => {
-                            let lit = meta.lit;
-                            let s = Ident::new(format!("{}", quote!(#lit)).as_str().trim_matches('"'), Span::call_site());
-                            Some(s)
-                        }
-                        _ => {
-                            panic!("invalid table_name attribute. must be in the form #[table_name = \"test\"]");
-                        }
-                    }
-                } else {
-                    acc
-                }
-            }
-            None => acc,
-        }
-    });
-
-    if table_name.is_none() {
-        panic!("#[table_name] attribute must be used when deriving from TableRow");
-    }
-
     let expanded = match input.data {
         Data::Struct(ref data) => match data.fields {
             Fields::Named(ref fields) => {
@@ -153,13 +54,24 @@ pub fn expand(input: TokenStream) -> TokenStream {
                         }
 
                         if is_secondary {
-                            secondary_keys.push((field.ident.clone(), field.ty.clone()));
+                            let ident = field.ident.clone().unwrap();
+                            let key = quote!(self.#ident);
+                            secondary_keys.push((ident, field.ty.clone(), key));
                         }
                     }
                 }
                 if primary_key.is_none() {
                     panic!("no primary key found");
                 }
+                // Keys declared on the struct come after the field keys, so
+                // adding one doesn't renumber the existing indexes
+                for attr in input.attrs.iter() {
+                    if let Some(Meta::List(ref list)) = attr.interpret_meta() {
+                        if list.ident == "secondary" {
+                            secondary_keys.push(computed_secondary_key(list));
+                        }
+                    }
+                }
                 if secondary_keys.len() > 16 {
                     panic!("up to 16 secondary keys are allowed");
                 }
@@ -168,10 +80,10 @@ pub fn expand(input: TokenStream) -> TokenStream {
                 let mut secondary_keys_constructors = quote!();
                 for i in 0..16 {
                     match secondary_keys.get(i) {
-                        Some((ident, ty)) => {
+                        Some((ident, ty, key)) => {
                             secondary_keys_expanded = quote! {
                                 #secondary_keys_expanded
-                                Some(&self.#ident),
+                                Some(#eosio::SecondaryKey::from(#key)),
                             };
                             secondary_keys_constructors = quote! {
                                 #secondary_keys_constructors
@@ -179,9 +91,10 @@ pub fn expand(input: TokenStream) -> TokenStream {
                                 pub fn #ident<C, S>(code: C, scope: S) -> #eosio::SecondaryTableIndex<#ty, Self>
                                 where
                                     C: Into<#eosio::AccountName>,
-                                    S: Into<#eosio::ScopeName>,
+                                    S: Into<#scope>,
                                 {
-                                    #eosio::SecondaryTableIndex::new(code, scope, #eosio::n!(#table_name), #ty::default(), #i)
+                                    let scope: #scope = scope.into();
+                                    #eosio::SecondaryTableIndex::new(code, scope, <Self as #eosio::TableRow>::TABLE_NAME, <#ty as Default>::default(), #i)
                                 }
                             };
                         }
@@ -194,25 +107,38 @@ pub fn expand(input: TokenStream) -> TokenStream {
                     };
                 }
 
+                // Indexes can only be opened in contracts, but the keys are
+                // also used off-chain, e.g. for RAM estimates
+                let secondary_keys_constructors = if cfg!(feature = "contract") {
+                    quote! {
+                        #[automatically_derived]
+                        impl #impl_generics #name #ty_generics #where_clause {
+                            #secondary_keys_constructors
+                        }
+                    }
+                } else {
+                    quote!()
+                };
+
                 quote! {
                     #[automatically_derived]
                     impl #impl_generics #eosio::TableRow for #name #ty_generics #where_clause {
-                        const TABLE_NAME: u64 = #eosio::n!(#table_name);
+                        const TABLE_NAME: #eosio::TableName = #eosio::TableName::new(#table_name);
+                        type Scope = #scope;
 
                         fn primary_key(&self) -> u64 {
                             self.#primary_key.into()
                         }
-                        fn secondary_keys(&self) -> [Option<&#eosio::SecondaryTableKey>; 16] {
+                        fn secondary_keys(&self) -> [Option<#eosio::SecondaryKey>; 16] {
                             [
                                 #secondary_keys_expanded
                             ]
                         }
                     }
 
-                    #[automatically_derived]
-                    impl #impl_generics #name #ty_generics #where_clause {
-                        #secondary_keys_constructors
-                    }
+                    #secondary_keys_constructors
+
+                    #versioned
                 }
             }
             _ => unimplemented!(),
@@ -222,3 +148,103 @@ pub fn expand(input: TokenStream) -> TokenStream {
 
     TokenStream::from(expanded)
 }
+
+/// A `#[secondary(name = "...", key = "...", ty = "...")]` attribute on the
+/// struct, for a key computed from the row. `ty` can be left out when `key`
+/// is a pair of 64-bit values, which are packed into a `u128`.
+fn computed_secondary_key(list: &MetaList) -> (Ident, Type, TokenStream2) {
+    let eosio = crate::paths::eosio();
+    let mut name = None;
+    let mut key = None;
+    let mut ty = None;
+    for nested in list.nested.iter() {
+        let value = match nested {
+            NestedMeta::Meta(Meta::NameValue(value)) => value,
+            _ => panic!("expected #[secondary(name = \"...\", key = \"...\")]"),
+        };
+        let lit = match value.lit {
+            Lit::Str(ref lit) => lit,
+            _ => panic!("#[secondary({} = ...)] must be a string", value.ident),
+        };
+        if value.ident == "name" {
+            name = Some(lit.parse::<Ident>().expect("invalid secondary key name"));
+        } else if value.ident == "key" {
+            key = Some(
+                lit.parse::<Expr>()
+                    .expect("invalid secondary key expression"),
+            );
+        } else if value.ident == "ty" {
+            ty = Some(lit.parse::<Type>().expect("invalid secondary key type"));
+        } else {
+            panic!("unknown #[secondary] argument `{}`", value.ident);
+        }
+    }
+    let name = name.expect("#[secondary] on a struct needs a name");
+    let key = key.expect("#[secondary] on a struct needs a key");
+    match (key, ty) {
+        (Expr::Tuple(ref tuple), None) if tuple.elems.len() == 2 => {
+            let high = &tuple.elems[0];
+            let low = &tuple.elems[1];
+            let key = quote!(#eosio::pack_u128(#high, #low));
+            (name, parse_quote!(u128), key)
+        }
+        (key, Some(ty)) => (name, ty, quote!(#key)),
+        (_, None) => panic!("secondary key `{}` needs a type, e.g. ty = \"u64\"", name),
+    }
+}
+
+/// The `#[table_version = N]` added by `#[eosio_table(name, version = N)]`.
+/// Also read by the `Read`, `Write` and `NumBytes` derives, which add the tag.
+pub fn table_version(attrs: &[Attribute]) -> Option<u8> {
+    attrs
+        .iter()
+        .filter_map(Attribute::interpret_meta)
+        .find(|meta| meta.name() == "table_version")
+        .map(|meta| match meta {
+            Meta::NameValue(ref meta) => match meta.lit {
+                Lit::Int(ref lit) if lit.value() >= 1 && lit.value() <= 255 => lit.value() as u8,
+                _ => panic!("table versions must be from 1 to 255"),
+            },
+            _ => {
+                panic!("invalid table_version attribute. must be in the form #[table_version = 2]")
+            }
+        })
+}
+
+/// The `#[table_name = "..."]` attribute, as a name
+fn table_name(attrs: &[Attribute]) -> Literal {
+    let mut names = attrs
+        .iter()
+        .filter_map(Attribute::interpret_meta)
+        .filter(|meta| meta.name() == "table_name");
+    let meta = names
+        .next()
+        .expect("#[table_name] attribute must be used when deriving from TableRow");
+    if names.next().is_some() {
+        panic!("only 1 table_name attribute allowed per struct");
+    }
+    let name = match meta {
+        Meta::NameValue(MetaNameValue {
+            lit: Lit::Str(ref lit),
+            ..
+        }) => lit.value(),
+        _ => panic!("invalid table_name attribute. must be in the form #[table_name = \"test\"]"),
+    };
+    let value =
+        string_to_name(&name).unwrap_or_else(|_| panic!("`{}` is not a valid table name", name));
+    Literal::u64_suffixed(value)
+}
+
+/// The type of the table's scope from `#[scope(AccountName)]`, which
+/// defaults to any `ScopeName`
+fn table_scope(attrs: &[Attribute]) -> Type {
+    let eosio = crate::paths::eosio();
+    let scope = attrs.iter().find(|attr| attr.path.is_ident("scope"));
+    match scope {
+        Some(attr) => match syn::parse2::<Type>(attr.tts.clone()) {
+            Ok(Type::Paren(ty)) => *ty.elem,
+            _ => panic!("invalid scope attribute. must be in the form #[scope(AccountName)]"),
+        },
+        None => parse_quote!(#eosio::ScopeName),
+    }
+}
@@ -59,6 +59,14 @@ pub fn expand(input: TokenStream) -> TokenStream {
         Data::Enum(_) | Data::Union(_) => unimplemented!(),
     };
 
+    let writes = match crate::derive_table_row::table_version(&input.attrs) {
+        Some(version) => quote! {
+            let pos = #eosio::Write::write(&#version, bytes, pos)?;
+            #writes
+        },
+        None => writes,
+    };
+
     let expanded = quote! {
         #[automatically_derived]
         impl #impl_generics #eosio::Write for #name #ty_generics #where_clause {
@@ -1,33 +1,68 @@
 use crate::proc_macro::TokenStream;
+use eosio_sys::string_to_name;
+#[cfg(feature = "contract")]
+use proc_macro2::Literal;
 use quote::quote;
-use syn::parse::{Parse, ParseStream, Result};
+use syn::parse::{Error, Parse, ParseStream, Result};
 use syn::punctuated::Punctuated;
-use syn::{parse_macro_input, Expr, Ident, Token};
+use syn::{parenthesized, parse_macro_input, Expr, Ident, LitStr, Token};
 
 #[cfg(not(feature = "contract"))]
 pub fn expand(input: TokenStream) -> TokenStream {
-    quote!().into()
+    let pairs = parse_macro_input!(input as AbiPairs);
+    client(&pairs.0).into()
 }
 
-struct AbiPair {
-    code: Option<Expr>,
-    action: Ident,
+#[cfg_attr(not(feature = "contract"), allow(dead_code))]
+enum AbiPair {
+    Action {
+        code: Option<Expr>,
+        action: Ident,
+    },
+    Notify {
+        pattern: NotifyPattern,
+        handler: Ident,
+    },
+    OnError {
+        handler: Ident,
+    },
+    Client {
+        ident: Ident,
+    },
 }
 
 impl Parse for AbiPair {
     fn parse(input: ParseStream) -> Result<Self> {
         let action: Ident = input.parse()?;
+        if action == "on_notify" {
+            let content;
+            parenthesized!(content in input);
+            let pattern: NotifyPattern = content.parse()?;
+            input.parse::<Token![=>]>()?;
+            let handler: Ident = input.parse()?;
+            return Ok(AbiPair::Notify { pattern, handler });
+        }
+        if action == "client" && input.peek(Token![=]) && !input.peek(Token![=>]) {
+            input.parse::<Token![=]>()?;
+            let ident: Ident = input.parse()?;
+            return Ok(AbiPair::Client { ident });
+        }
+        if action == "on_error" {
+            input.parse::<Token![=>]>()?;
+            let handler: Ident = input.parse()?;
+            return Ok(AbiPair::OnError { handler });
+        }
         // Ok(AbiPair { code: None, action })
         // TODO
         match input.parse::<Token![@]>() {
             Ok(_) => {
                 let code: Expr = input.parse()?;
-                Ok(AbiPair {
+                Ok(AbiPair::Action {
                     code: Some(code),
                     action,
                 })
             }
-            Err(_) => Ok(AbiPair { code: None, action }),
+            Err(_) => Ok(AbiPair::Action { code: None, action }),
         }
     }
 }
@@ -38,34 +73,155 @@ impl Parse for AbiPairs {
     fn parse(input: ParseStream) -> Result<Self> {
         let parsed = Punctuated::<AbiPair, Token![,]>::parse_separated_nonempty(input)?;
         let pairs: Vec<AbiPair> = parsed.into_iter().collect();
+        let mut on_error = pairs.iter().filter_map(|pair| match pair {
+            AbiPair::OnError { handler } => Some(handler),
+            _ => None,
+        });
+        if let (Some(_), Some(handler)) = (on_error.next(), on_error.next()) {
+            return Err(Error::new(
+                handler.span(),
+                "only one on_error handler is allowed",
+            ));
+        }
         Ok(AbiPairs(pairs))
     }
 }
 
+/// A `"code::action"` notification pattern, where `code` may be `*` to match
+/// notifications from any account.
+#[cfg_attr(not(feature = "contract"), allow(dead_code))]
+pub(crate) struct NotifyPattern {
+    code: Option<u64>,
+    action: u64,
+}
+
+impl Parse for NotifyPattern {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let lit: LitStr = input.parse()?;
+        let value = lit.value();
+        let mut parts = value.splitn(2, "::");
+        let code = parts.next().unwrap_or_default();
+        let action = match parts.next() {
+            Some(action) => action,
+            None => {
+                return Err(Error::new(
+                    lit.span(),
+                    "expected \"code::action\" or \"*::action\"",
+                ))
+            }
+        };
+        let to_name = |s: &str| {
+            string_to_name(s)
+                .map_err(|_| Error::new(lit.span(), format!("invalid EOSIO name '{}'", s)))
+        };
+        let code = if code == "*" {
+            None
+        } else {
+            Some(to_name(code)?)
+        };
+        let action = to_name(action)?;
+        Ok(NotifyPattern { code, action })
+    }
+}
+
+impl NotifyPattern {
+    /// The condition for `apply` to dispatch to a notification handler. This
+    /// never matches when `code == receiver`, so a notification handler can't
+    /// be triggered by pushing the action directly to this contract.
+    #[cfg(feature = "contract")]
+    pub(crate) fn condition(&self) -> ::proc_macro2::TokenStream {
+        let action = Literal::u64_suffixed(self.action);
+        match self.code {
+            Some(code) => {
+                let code = Literal::u64_suffixed(code);
+                quote!(code != receiver && code == #code && action == #action)
+            }
+            None => quote!(code != receiver && action == #action),
+        }
+    }
+}
+
+/// The client struct given by `client = ...`. It gets a method for every
+/// action from the `{Name}Client` traits of the `#[eosio_action]` functions.
+fn client(pairs: &[AbiPair]) -> ::proc_macro2::TokenStream {
+    let ident = pairs.iter().find_map(|pair| match pair {
+        AbiPair::Client { ident } => Some(ident),
+        _ => None,
+    });
+    match ident {
+        Some(ident) => crate::eosio_action::client_struct(ident),
+        None => quote!(),
+    }
+}
+
+/// Reads the action data as the struct behind `alias` and executes it
+#[cfg(feature = "contract")]
+fn execute_action(alias: &Ident) -> ::proc_macro2::TokenStream {
+    let eosio = crate::paths::eosio();
+    quote! {
+        let (data, _) = #eosio::Assert::assert(
+            <#alias as #eosio::ActionFn>::read_data(),
+            "read"
+        );
+        #eosio::ActionFn::execute(data);
+    }
+}
+
 #[cfg(feature = "contract")]
 pub fn expand(input: TokenStream) -> TokenStream {
     let pairs = parse_macro_input!(input as AbiPairs);
     let eosio = crate::paths::eosio();
-    let actions = pairs.0.into_iter().map(|pair| {
-        let code = pair
-            .code
-            .map(|code| quote!(#eosio::n!(#code)))
-            .unwrap_or_else(|| quote!(receiver));
-        let action = pair.action;
-        quote! {
-            else if code == #code && action == #eosio::n!(#action) {
-                #action();
-            }
+    let client = client(&pairs.0);
+    let actions = pairs.0.iter().filter_map(|pair| match pair {
+        AbiPair::Action { code, action } => {
+            let code = code
+                .as_ref()
+                .map(|code| quote!(#eosio::n!(#code)))
+                .unwrap_or_else(|| quote!(receiver));
+            let alias = crate::eosio_action::action_alias(action);
+            let execute = execute_action(&alias);
+            Some(quote! {
+                else if code == #code && action == <#alias as #eosio::ToAction>::NAME {
+                    #execute
+                }
+            })
         }
+        AbiPair::Notify { pattern, handler } => {
+            let condition = pattern.condition();
+            let alias = crate::eosio_action::action_alias(handler);
+            let execute = execute_action(&alias);
+            Some(quote! {
+                else if #condition {
+                    #execute
+                }
+            })
+        }
+        AbiPair::OnError { .. } | AbiPair::Client { .. } => None,
+    });
+    // `onerror` is handled before the other actions, since it always comes
+    // from `eosio` and so never matches them
+    let on_error = pairs.0.iter().find_map(|pair| match pair {
+        AbiPair::OnError { handler } => Some(quote! {
+            let (error, _) = #eosio::Assert::assert(
+                #eosio::read_action_data::<#eosio::OnError>(),
+                "read"
+            );
+            #handler(error);
+        }),
+        _ => None,
     });
     let expanded = quote! {
+        #client
+
         #[no_mangle]
         pub extern "C" fn apply(receiver: u64, code: u64, action: u64) {
+            #eosio::runtime::init();
             if action == #eosio::n!(onerror) {
                 #eosio::eosio_assert(
                     code == #eosio::n!(eosio),
                     "onerror action's are only valid from the \"eosio\" system account"
                 );
+                #on_error
             }
             #(#actions)*
             else if code == receiver {
@@ -1,56 +1,334 @@
 use crate::proc_macro::TokenStream;
+use eosio_sys::string_to_name;
+use proc_macro2::{Delimiter, TokenTree};
 use quote::quote;
-use syn::{parse_macro_input, FnArg, Ident, ItemFn};
-
+use syn::ext::IdentExt;
+use syn::parse::{Error, Parse, ParseStream, Parser, Result};
 #[cfg(feature = "contract")]
-pub fn expand(_args: TokenStream, input: TokenStream) -> TokenStream {
-    let input = parse_macro_input!(input as ItemFn);
-    let eosio = crate::paths::eosio();
-    let ident = input.ident;
-    let decl = input.decl;
-    let inputs = decl.inputs;
-    let vis = input.vis;
-    let mut struct_fields = quote!();
-    let mut assign_args = quote!();
-    for input in inputs.iter() {
-        match input {
-            FnArg::Captured(input) => {
-                let pat = &input.pat;
-                let ty = &input.ty;
-                let ty_str = quote!(#ty).to_string();
-                let serde_attr = if ty_str == "bool" {
-                    quote!(
-                        #[cfg_attr(
-                            feature = "serde",
-                            serde(
-                                deserialize_with = "::eosio::json::bool_from_u8",
-                                serialize_with = "::eosio::json::bool_to_u8"
-                            )
-                        )]
-                    )
-                } else {
-                    quote!()
-                };
-                struct_fields = quote! {
-                    #struct_fields
-                    #serde_attr
-                    pub #pat: #ty,
-                };
-                assign_args = quote! {
-                    #assign_args
-                    let #pat = self.#pat;
-                };
+use syn::parse_quote;
+use syn::punctuated::Punctuated;
+use syn::{
+    bracketed, parenthesized, parse_macro_input, token, ArgCaptured, Attribute, Block, Expr, FnArg,
+    GenericArgument, Ident, ItemFn, LitStr, PathArguments, ReturnType, Token, Type,
+};
+
+/// The arguments to `#[eosio_action(...)]`
+#[derive(Default)]
+#[cfg_attr(not(feature = "contract"), allow(dead_code))]
+pub(crate) struct ActionArgs {
+    /// Arguments that must authorize the action, with an optional permission
+    auth: Vec<(Ident, Option<u64>)>,
+    /// Arguments to notify of the action
+    notify: Vec<Ident>,
+    /// The action name, if it isn't the function name
+    name: Option<u64>,
+    /// The action struct, if it isn't `{Titlecase}Action`
+    struct_ident: Option<Ident>,
+    /// Whether the action runs without authorization or database access,
+    /// which `check_context_free` lints for
+    context_free: bool,
+    /// Whether the function is also emitted without the `contract` feature,
+    /// for actions whose bodies don't use contract-only APIs
+    off_chain: bool,
+}
+
+impl Parse for ActionArgs {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut args = ActionArgs::default();
+        while !input.is_empty() {
+            let key = input.call(Ident::parse_any)?;
+            if key == "context_free" {
+                args.context_free = true;
+            } else if key == "off_chain" {
+                args.off_chain = true;
+            } else if key == "name" {
+                input.parse::<Token![=]>()?;
+                let lit: LitStr = input.parse()?;
+                let name = string_to_name(lit.value().as_str())
+                    .map_err(|_| Error::new(lit.span(), "invalid action name"))?;
+                args.name = Some(name);
+            } else if key == "struct" {
+                input.parse::<Token![=]>()?;
+                let lit: LitStr = input.parse()?;
+                let mut ident = syn::parse_str::<Ident>(lit.value().as_str())
+                    .map_err(|_| Error::new(lit.span(), "expected a struct name"))?;
+                ident.set_span(lit.span());
+                args.struct_ident = Some(ident);
+            } else if key == "auth" {
+                input.parse::<Token![=]>()?;
+                for lit in parse_strings(input)? {
+                    let value = lit.value();
+                    let mut parts = value.splitn(2, '@');
+                    let account = parse_arg_ident(parts.next().unwrap_or_default(), &lit)?;
+                    let permission = match parts.next() {
+                        Some(permission) => Some(
+                            string_to_name(permission)
+                                .map_err(|_| Error::new(lit.span(), "invalid permission name"))?,
+                        ),
+                        None => None,
+                    };
+                    args.auth.push((account, permission));
+                }
+            } else if key == "notify" {
+                input.parse::<Token![=]>()?;
+                for lit in parse_strings(input)? {
+                    args.notify.push(parse_arg_ident(&lit.value(), &lit)?);
+                }
+            } else {
+                return Err(Error::new(
+                    key.span(),
+                    format!("unknown #[eosio_action] argument `{}`", key),
+                ));
+            }
+
+            if input.is_empty() {
+                break;
             }
-            _ => unimplemented!(),
+            input.parse::<Token![,]>()?;
+        }
+        if args.context_free && (!args.auth.is_empty() || !args.notify.is_empty()) {
+            return Err(Error::new(
+                ::proc_macro2::Span::call_site(),
+                "context-free actions can't use `auth` or `notify`",
+            ));
         }
+        Ok(args)
     }
-    let block = input.block;
+}
+
+/// Parses `"a"` or `["a", "b"]`
+fn parse_strings(input: ParseStream) -> Result<Vec<LitStr>> {
+    if input.peek(token::Bracket) {
+        let content;
+        bracketed!(content in input);
+        let strings = Punctuated::<LitStr, Token![,]>::parse_terminated(&content)?;
+        Ok(strings.into_iter().collect())
+    } else {
+        Ok(vec![input.parse()?])
+    }
+}
 
-    let call_site = ::proc_macro2::Span::call_site();
-    let struct_name = titlecase(ident.to_string().as_str());
-    let struct_ident = Ident::new(format!("{}Action", struct_name).as_str(), call_site);
+fn parse_arg_ident(value: &str, lit: &LitStr) -> Result<Ident> {
+    let mut ident = syn::parse_str::<Ident>(value)
+        .map_err(|_| Error::new(lit.span(), "expected the name of an argument"))?;
+    ident.set_span(lit.span());
+    Ok(ident)
+}
+
+/// A `#[check(condition)]` or `#[check(condition, "message")]` attribute
+#[cfg_attr(not(feature = "contract"), allow(dead_code))]
+pub(crate) struct Check {
+    condition: Expr,
+    message: String,
+}
+
+impl Parse for Check {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let content;
+        parenthesized!(content in input);
+        let condition: Expr = content.parse()?;
+        let message = if content.peek(Token![,]) {
+            content.parse::<Token![,]>()?;
+            content.parse::<LitStr>()?.value()
+        } else {
+            format!("check failed: {}", quote!(#condition))
+        };
+        Ok(Check { condition, message })
+    }
+}
+
+/// Removes the `#[check]` attributes from `attrs`
+pub(crate) fn take_checks(attrs: &mut Vec<Attribute>) -> Vec<Check> {
+    let mut checks = Vec::new();
+    let mut rest = Vec::new();
+    for attr in attrs.drain(..) {
+        if attr.path.is_ident("check") {
+            match Check::parse.parse2(attr.tts) {
+                Ok(check) => checks.push(check),
+                Err(e) => panic!("invalid #[check] attribute: {}", e),
+            }
+        } else {
+            rest.push(attr);
+        }
+    }
+    *attrs = rest;
+    checks
+}
+
+/// Panics if the body of the context-free action `ident` calls an
+/// authorization or database API by name.
+///
+/// This is a best-effort lint, not a guarantee: it only matches names in the
+/// function itself, so calls made through helper functions, table cursors or
+/// index handles passed in from elsewhere aren't caught. `nodeos` still
+/// rejects those at runtime. Functions defined in the body and method calls
+/// are never flagged, whatever their names.
+pub(crate) fn check_context_free(ident: &Ident, block: &Block) {
+    fn punct(token: Option<&TokenTree>) -> Option<char> {
+        match token {
+            Some(TokenTree::Punct(punct)) => Some(punct.as_char()),
+            _ => None,
+        }
+    }
+
+    /// The names of the functions defined in `tokens`
+    fn local_fns(tokens: ::proc_macro2::TokenStream, names: &mut Vec<String>) {
+        let mut after_fn = false;
+        for token in tokens {
+            match token {
+                TokenTree::Group(ref group) => local_fns(group.stream(), names),
+                TokenTree::Ident(ref name) if after_fn => names.push(name.to_string()),
+                _ => (),
+            }
+            after_fn = match token {
+                TokenTree::Ident(ref name) => name == "fn",
+                _ => false,
+            };
+        }
+    }
+
+    fn check(ident: &Ident, tokens: ::proc_macro2::TokenStream, local: &[String]) {
+        let tokens: Vec<TokenTree> = tokens.into_iter().collect();
+        for (i, token) in tokens.iter().enumerate() {
+            let used = match token {
+                TokenTree::Group(group) => {
+                    check(ident, group.stream(), local);
+                    continue;
+                }
+                TokenTree::Ident(used) => used,
+                _ => continue,
+            };
+            let name = used.to_string();
+            if local.contains(&name) {
+                continue;
+            }
+            let prev = if i > 0 {
+                punct(tokens.get(i - 1))
+            } else {
+                None
+            };
+            let next = punct(tokens.get(i + 1));
+            let is_call = match tokens.get(i + 1) {
+                Some(TokenTree::Group(group)) => group.delimiter() == Delimiter::Parenthesis,
+                _ => false,
+            };
+            // `::name`, `name::`, `name<` or `: name`, i.e. a path or a type
+            let is_path = prev == Some(':')
+                || (next == Some(':') && punct(tokens.get(i + 2)) == Some(':'))
+                || next == Some('<');
+            let is_forbidden = if prev == Some('.') {
+                false
+            } else if CONTEXT_FREE_CONSTRUCTORS.contains(&name.as_str()) {
+                prev == Some(':') && is_call
+            } else if name.starts_with("db_") {
+                is_call
+            } else {
+                CONTEXT_FREE_FORBIDDEN.contains(&name.as_str()) && (is_call || is_path)
+            };
+            if is_forbidden {
+                panic!(
+                    "`{}` can't be used in the context-free action `{}`",
+                    name, ident
+                );
+            }
+        }
+    }
+    let mut local = Vec::new();
+    local_fns(quote!(#block), &mut local);
+    check(ident, quote!(#block), &local);
+}
+
+/// Authorization and database APIs
+const CONTEXT_FREE_FORBIDDEN: &[&str] = &[
+    "require_auth",
+    "require_auth2",
+    "require_permission",
+    "require_recipient",
+    "has_auth",
+    "is_account",
+    "TableRow",
+    "SingletonRow",
+    "PrimaryTableIndex",
+    "SecondaryTableIndex",
+    "Singleton",
+];
+
+/// Functions that open a table, e.g. `Row::table(code, scope)`
+const CONTEXT_FREE_CONSTRUCTORS: &[&str] = &["table", "singleton"];
+
+/// The authorization checks, `#[check]`s and notifications that run when an
+/// action is dispatched, before its function is called. Calling the function
+/// directly, e.g. from another action, skips them.
+#[cfg_attr(not(feature = "contract"), allow(dead_code))]
+pub(crate) fn preconditions(args: &ActionArgs, checks: &[Check]) -> ::proc_macro2::TokenStream {
+    let eosio = crate::paths::eosio();
+    let auths = args
+        .auth
+        .iter()
+        .map(|(account, permission)| match permission {
+            Some(permission) => quote!(#eosio::require_permission(#account, #permission);),
+            None => quote!(#eosio::require_auth(#account);),
+        });
+    let checks = checks.iter().map(|check| {
+        let condition = &check.condition;
+        let message = &check.message;
+        quote!(#eosio::eosio_assert(#condition, #message);)
+    });
+    let notify = args
+        .notify
+        .iter()
+        .map(|account| quote!(#eosio::require_recipient(#account);));
+    quote! {
+        #(#auths)*
+        #(#checks)*
+        #(#notify)*
+    }
+}
+
+#[cfg(feature = "contract")]
+pub fn expand(args: TokenStream, input: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(args as ActionArgs);
+    let mut input = parse_macro_input!(input as ItemFn);
+    let checks = take_checks(&mut input.attrs);
+    if args.context_free {
+        check_context_free(&input.ident, &input.block);
+    }
+    let preconditions = preconditions(&args, &checks);
+    // Arguments may only be used by the preconditions, e.g. a memo that is
+    // only checked for its length
+    if !preconditions.is_empty() {
+        input.attrs.push(parse_quote!(#[allow(unused_variables)]));
+    }
+    let eosio = crate::paths::eosio();
+
+    let action = ActionFnParts::new(&args, &input);
+    let struct_ident = &action.struct_ident;
+    let struct_fields = &action.fields;
+    let pats = &action.pats;
+    let name = action.name;
+    let return_type = return_type(&input.decl.output);
+    let alias = action_alias(&input.ident);
+    let context_free = context_free(&args);
+    let client = client_trait(
+        &input.ident,
+        struct_ident,
+        pats,
+        &action.types,
+        args.context_free,
+    );
+
+    let ident = &input.ident;
+    let call = match input.decl.output {
+        ReturnType::Default => quote!(#ident(#(#pats),*);),
+        ReturnType::Type(..) => quote! {
+            let value = #ident(#(#pats),*);
+            #eosio::Assert::assert(#eosio::set_action_return_value(&value), "write");
+        },
+    };
 
     let expanded = quote! {
+        #input
+
         #[derive(Clone, #eosio::Read, #eosio::Write, #eosio::NumBytes)]
         #[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
         pub struct #struct_ident {
@@ -59,71 +337,62 @@ pub fn expand(_args: TokenStream, input: TokenStream) -> TokenStream {
 
         #[automatically_derived]
         impl #eosio::ToAction for #struct_ident {
-            const NAME: u64 = n!(#ident);
+            const NAME: u64 = #name;
+            #return_type
+            #context_free
         }
 
         #[automatically_derived]
         impl #eosio::ActionFn for #struct_ident {
             fn execute(self) {
-                #(#assign_args)*
-                #block
+                let #struct_ident { #(#pats),* } = self;
+                #preconditions
+                #call
             }
         }
 
-        // TODO: keep original function intact so it can be called like normal
-        #vis fn #ident() {
-            let (s, _) = #struct_ident::read_data().assert("read");
-            s.execute();
-        }
+        #[doc(hidden)]
+        #[allow(non_camel_case_types)]
+        pub type #alias = #struct_ident;
+
+        #client
     };
-    TokenStream::from(quote!(#expanded))
-    // input
+    TokenStream::from(expanded)
 }
 
 #[cfg(not(feature = "contract"))]
-pub fn expand(_args: TokenStream, input: TokenStream) -> TokenStream {
-    let input = parse_macro_input!(input as ItemFn);
-    let eosio = crate::paths::eosio();
-    let ident = input.ident;
-    let decl = input.decl;
-    let inputs = decl.inputs;
-    let vis = input.vis;
-    let mut struct_fields = quote!();
-    let mut assign_args = quote!();
-    for input in inputs.iter() {
-        match input {
-            FnArg::Captured(input) => {
-                let pat = &input.pat;
-                let ty = &input.ty;
-                let ty_str = quote!(#ty).to_string();
-                let serde_attr = if ty_str == "bool" {
-                    quote!(
-                        #[cfg_attr(
-                            feature = "serde",
-                            serde(
-                                deserialize_with = "::eosio::json::bool_from_u8",
-                                serialize_with = "::eosio::json::bool_to_u8"
-                            )
-                        )]
-                    )
-                } else {
-                    quote!()
-                };
-                struct_fields = quote! {
-                    #struct_fields
-                    #serde_attr
-                    pub #pat: #ty,
-                };
-            }
-            _ => unimplemented!(),
-        }
+pub fn expand(args: TokenStream, input: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(args as ActionArgs);
+    let mut input = parse_macro_input!(input as ItemFn);
+    let _checks = take_checks(&mut input.attrs);
+    if args.context_free {
+        check_context_free(&input.ident, &input.block);
     }
+    let eosio = crate::paths::eosio();
 
-    let call_site = ::proc_macro2::Span::call_site();
-    let struct_name = titlecase(ident.to_string().as_str());
-    let struct_ident = Ident::new(format!("{}Action", struct_name).as_str(), call_site);
+    let action = ActionFnParts::new(&args, &input);
+    let struct_ident = &action.struct_ident;
+    let struct_fields = &action.fields;
+    let name = action.name;
+    let return_type = return_type(&input.decl.output);
+    let alias = action_alias(&input.ident);
+    let context_free = context_free(&args);
+    let client = client_trait(
+        &input.ident,
+        struct_ident,
+        &action.pats,
+        &action.types,
+        args.context_free,
+    );
+    let function = if args.off_chain {
+        quote!(#input)
+    } else {
+        quote!()
+    };
 
     let expanded = quote! {
+        #function
+
         #[derive(Clone, #eosio::Read, #eosio::Write, #eosio::NumBytes, Default)]
         #[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
         pub struct #struct_ident {
@@ -132,16 +401,288 @@ pub fn expand(_args: TokenStream, input: TokenStream) -> TokenStream {
 
         #[automatically_derived]
         impl #eosio::ToAction for #struct_ident {
-            const NAME: u64 = n!(#ident);
+            const NAME: u64 = #name;
+            #return_type
+            #context_free
         }
 
-        #vis fn #ident() { }
+        #[doc(hidden)]
+        #[allow(non_camel_case_types)]
+        pub type #alias = #struct_ident;
+
+        #client
+    };
+    TokenStream::from(expanded)
+}
+
+/// The action struct generated for an `#[eosio_action]` function
+struct ActionFnParts {
+    struct_ident: Ident,
+    name: u64,
+    fields: Vec<::proc_macro2::TokenStream>,
+    pats: Vec<::proc_macro2::TokenStream>,
+    types: Vec<Type>,
+}
+
+impl ActionFnParts {
+    fn new(args: &ActionArgs, input: &ItemFn) -> Self {
+        let ident = &input.ident;
+        let mut fields = Vec::new();
+        let mut pats = Vec::new();
+        let mut types = Vec::new();
+        for input in input.decl.inputs.iter() {
+            match input {
+                FnArg::Captured(input) => {
+                    fields.push(struct_field(input));
+                    let pat = &input.pat;
+                    pats.push(quote!(#pat));
+                    types.push(input.ty.clone());
+                }
+                _ => unimplemented!(),
+            }
+        }
+
+        let name = match args.name {
+            Some(name) => name,
+            None => string_to_name(ident.to_string().as_str()).unwrap_or_else(|_| {
+                panic!(
+                    "`{}` is not a valid action name, use #[eosio_action(name = \"...\")]",
+                    ident
+                )
+            }),
+        };
+
+        let struct_ident = args.struct_ident.clone().unwrap_or_else(|| {
+            let struct_name = titlecase(ident.to_string().as_str());
+            Ident::new(
+                format!("{}Action", struct_name).as_str(),
+                ::proc_macro2::Span::call_site(),
+            )
+        });
+
+        ActionFnParts {
+            struct_ident,
+            name,
+            fields,
+            pats,
+            types,
+        }
+    }
+}
+
+/// A hidden alias for the action struct of the `#[eosio_action]` function
+/// `ident`, which lets `eosio_abi!` find the struct from the function name.
+pub(crate) fn action_alias(ident: &Ident) -> Ident {
+    Ident::new(format!("__eosio_action_{}", ident).as_str(), ident.span())
+}
+
+/// A public `{Name}Client` trait that adds the client method for the
+/// `#[eosio_action]` function `ident` to every `Client`, so any crate can
+/// build the action, e.g. with the client struct from `eosio_abi!`.
+pub(crate) fn client_trait(
+    ident: &Ident,
+    struct_ident: &Ident,
+    pats: &[::proc_macro2::TokenStream],
+    types: &[Type],
+    context_free: bool,
+) -> ::proc_macro2::TokenStream {
+    let eosio = crate::paths::eosio();
+    let trait_ident = Ident::new(
+        format!("{}Client", titlecase(ident.to_string().as_str())).as_str(),
+        ident.span(),
+    );
+    let method = client_method(ident, struct_ident, pats, types, context_free, quote!());
+    let doc = format!("Adds `{}` to every `Client`", ident);
+    quote! {
+        #[doc = #doc]
+        pub trait #trait_ident: #eosio::Client {
+            #method
+        }
+
+        impl<C> #trait_ident for C where C: #eosio::Client {}
+    }
+}
+
+/// A client method that builds the action, taking the authorization followed
+/// by the action arguments. Context-free actions don't take an authorization.
+pub(crate) fn client_method(
+    method: &Ident,
+    struct_ident: &Ident,
+    pats: &[::proc_macro2::TokenStream],
+    types: &[Type],
+    context_free: bool,
+    vis: ::proc_macro2::TokenStream,
+) -> ::proc_macro2::TokenStream {
+    let eosio = crate::paths::eosio();
+    let doc = format!("Builds a `{}` action", method);
+    if context_free {
+        return quote! {
+            #[doc = #doc]
+            #vis fn #method(
+                &self,
+                #(#pats: #types),*
+            ) -> #eosio::ContextFreeAction<#struct_ident> {
+                let data = #struct_ident { #(#pats),* };
+                let account = #eosio::Client::account(self);
+                #eosio::ToAction::to_context_free_action(data, account)
+            }
+        };
+    }
+    quote! {
+        #[doc = #doc]
+        #vis fn #method<A>(
+            &self,
+            authorization: A,
+            #(#pats: #types),*
+        ) -> #eosio::Action<#struct_ident>
+        where
+            A: Into<Vec<#eosio::Authorization>>,
+        {
+            let data = #struct_ident { #(#pats),* };
+            let account = #eosio::Client::account(self);
+            #eosio::ToAction::to_action(data, account, authorization.into())
+        }
+    }
+}
+
+/// A client struct for sending actions to a contract, e.g. inline from
+/// another contract
+pub(crate) fn client_struct(ident: &Ident) -> ::proc_macro2::TokenStream {
+    let eosio = crate::paths::eosio();
+    quote! {
+        /// Builds actions to send to this contract
+        #[derive(Clone, Copy, Debug)]
+        pub struct #ident {
+            pub account: #eosio::AccountName,
+        }
+
+        impl #ident {
+            pub fn new<A>(account: A) -> Self
+            where
+                A: Into<#eosio::AccountName>,
+            {
+                #ident {
+                    account: account.into(),
+                }
+            }
+        }
+
+        impl #eosio::Client for #ident {
+            fn account(&self) -> #eosio::AccountName {
+                self.account
+            }
+        }
+    }
+}
+
+/// The action struct field for a function argument. `bool` fields are
+/// (de)serialized as `0`/`1` to match `nodeos`.
+pub(crate) fn struct_field(input: &ArgCaptured) -> ::proc_macro2::TokenStream {
+    let pat = &input.pat;
+    let ty = &input.ty;
+    let ty_str = quote!(#ty).to_string();
+    let serde_attr = if ty_str == "bool" {
+        quote!(
+            #[cfg_attr(
+                feature = "serde",
+                serde(
+                    deserialize_with = "::eosio::json::bool_from_u8",
+                    serialize_with = "::eosio::json::bool_to_u8"
+                )
+            )]
+        )
+    } else {
+        quote!()
+    };
+    quote! {
+        #serde_attr
+        pub #pat: #ty,
+    }
+}
+
+/// The `CONTEXT_FREE` of an action struct
+fn context_free(args: &ActionArgs) -> ::proc_macro2::TokenStream {
+    if args.context_free {
+        quote!(
+            const CONTEXT_FREE: bool = true;
+        )
+    } else {
+        quote!()
+    }
+}
+
+/// The `RETURN_TYPE` of an action struct, for actions that return a value
+pub(crate) fn return_type(output: &ReturnType) -> ::proc_macro2::TokenStream {
+    match output {
+        ReturnType::Default => quote!(),
+        ReturnType::Type(_, ty) => {
+            let abi_type = abi_type(ty);
+            quote!(const RETURN_TYPE: Option<&'static str> = Some(#abi_type);)
+        }
+    }
+}
+
+/// The ABI type name for a Rust type, e.g. `u64` is `uint64` and `Vec<T>` is
+/// `T[]`. Unknown types keep their Rust name.
+pub(crate) fn abi_type(ty: &Type) -> String {
+    let segment = match ty {
+        Type::Reference(reference) => return abi_type(&reference.elem),
+        Type::Paren(paren) => return abi_type(&paren.elem),
+        Type::Path(path) => match path.path.segments.iter().last() {
+            Some(segment) => segment,
+            None => return quote!(#ty).to_string(),
+        },
+        _ => return quote!(#ty).to_string(),
+    };
+    let ident = segment.ident.to_string();
+    let inner = match segment.arguments {
+        PathArguments::AngleBracketed(ref args) => match args.args.iter().next() {
+            Some(GenericArgument::Type(inner)) => Some(inner),
+            _ => None,
+        },
+        _ => None,
+    };
+    let name = match (ident.as_str(), inner) {
+        ("Vec", Some(inner)) => {
+            let inner = abi_type(inner);
+            return if inner == "uint8" {
+                "bytes".to_string()
+            } else {
+                format!("{}[]", inner)
+            };
+        }
+        ("Option", Some(inner)) => return format!("{}?", abi_type(inner)),
+        ("bool", _) => "bool",
+        ("u8", _) => "uint8",
+        ("u16", _) => "uint16",
+        ("u32", _) => "uint32",
+        ("u64", _) => "uint64",
+        ("u128", _) => "uint128",
+        ("i16", _) => "int16",
+        ("i32", _) => "int32",
+        ("i64", _) => "int64",
+        ("i128", _) => "int128",
+        ("f32", _) => "float32",
+        ("f64", _) => "float64",
+        ("Float128", _) => "float128",
+        ("String", _) | ("str", _) => "string",
+        ("UnsignedInt", _) => "varuint32",
+        ("AccountName", _) | ("ActionName", _) | ("PermissionName", _) => "name",
+        ("Time", _) => "time_point",
+        ("Asset", _) => "asset",
+        ("ExtendedAsset", _) => "extended_asset",
+        ("Symbol", _) => "symbol",
+        ("SymbolName", _) => "symbol_code",
+        ("PublicKey", _) => "public_key",
+        ("Ripemd160", _) => "checksum160",
+        ("Sha256", _) => "checksum256",
+        ("Sha512", _) => "checksum512",
+        (ident, _) => ident,
     };
-    TokenStream::from(quote!(#expanded))
-    // input
+    name.to_string()
 }
 
-fn titlecase(s: &str) -> String {
+pub(crate) fn titlecase(s: &str) -> String {
     let mut c = s.chars();
     match c.next() {
         None => String::new(),
@@ -0,0 +1,385 @@
+use crate::eosio_abi::NotifyPattern;
+use crate::proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::quote;
+use syn::parse::{ParseStream, Parser};
+use syn::{
+    parenthesized, parse_macro_input, Expr, Fields, FnArg, Ident, ImplItem, Item, ItemImpl,
+    ItemStruct, Pat, ReturnType, Type, Visibility,
+};
+
+/// A `pub fn`, `#[on_notify]` or `#[on_error]` handler in a `#[eosio_contract]`
+/// impl block
+struct ContractAction {
+    method: Ident,
+    struct_ident: Ident,
+    fields: Vec<TokenStream2>,
+    args: Vec<Ident>,
+    types: Vec<Type>,
+    output: ReturnType,
+    notify: Option<NotifyPattern>,
+    on_error: bool,
+    /// Whether the method takes `&mut self`
+    #[cfg_attr(not(feature = "contract"), allow(dead_code))]
+    mutable: bool,
+}
+
+/// Collects the actions and notification handlers in `item`, removing the
+/// `#[on_notify]` and `#[on_error]` attributes so the impl block can be emitted as is.
+fn contract_actions(item: &mut ItemImpl) -> Vec<ContractAction> {
+    let call_site = Span::call_site();
+    let mut actions = Vec::new();
+    for impl_item in item.items.iter_mut() {
+        let method = match impl_item {
+            ImplItem::Method(method) => method,
+            _ => continue,
+        };
+
+        let mut notify = None;
+        let mut on_error = false;
+        let mut attrs = Vec::new();
+        for attr in method.attrs.drain(..) {
+            if attr.path.is_ident("on_notify") {
+                let parser = |input: ParseStream| {
+                    let content;
+                    parenthesized!(content in input);
+                    content.parse::<NotifyPattern>()
+                };
+                match parser.parse2(attr.tts) {
+                    Ok(pattern) => notify = Some(pattern),
+                    Err(e) => panic!("invalid #[on_notify] attribute: {}", e),
+                }
+            } else if attr.path.is_ident("on_error") {
+                on_error = true;
+            } else {
+                attrs.push(attr);
+            }
+        }
+        method.attrs = attrs;
+
+        match method.vis {
+            Visibility::Public(_) => (),
+            _ if notify.is_some() || on_error => (),
+            _ => continue,
+        }
+
+        let ident = method.sig.ident.clone();
+        let mut has_self = false;
+        let mut mutable = false;
+        let mut fields = Vec::new();
+        let mut args = Vec::new();
+        let mut types = Vec::new();
+        for input in method.sig.decl.inputs.iter() {
+            match input {
+                FnArg::SelfRef(self_ref) => {
+                    has_self = true;
+                    mutable = self_ref.mutability.is_some();
+                }
+                FnArg::Captured(input) => {
+                    match input.pat {
+                        Pat::Ident(ref pat) => args.push(pat.ident.clone()),
+                        _ => panic!("action arguments must be identifiers"),
+                    }
+                    fields.push(crate::eosio_action::struct_field(input));
+                    types.push(input.ty.clone());
+                }
+                _ => panic!("actions must take `&self` or `&mut self` followed by named arguments"),
+            }
+        }
+        if !has_self {
+            panic!("action `{}` must take `&self` or `&mut self`", ident);
+        }
+
+        let struct_name = crate::eosio_action::titlecase(ident.to_string().as_str());
+        let struct_ident = Ident::new(format!("{}Action", struct_name).as_str(), call_site);
+        actions.push(ContractAction {
+            method: ident,
+            struct_ident,
+            fields,
+            args,
+            types,
+            output: method.sig.decl.output.clone(),
+            notify,
+            on_error,
+            mutable,
+        });
+    }
+    let mut on_error = actions.iter().filter(|action| action.on_error);
+    if let (Some(first), Some(second)) = (on_error.next(), on_error.next()) {
+        panic!(
+            "only one #[on_error] handler is allowed, found `{}` and `{}`",
+            first.method, second.method
+        );
+    }
+    actions
+}
+
+fn action_structs(actions: &[ContractAction]) -> TokenStream2 {
+    let eosio = crate::paths::eosio();
+    let default = if cfg!(feature = "contract") {
+        quote!()
+    } else {
+        quote!(Default,)
+    };
+    let structs = actions
+        .iter()
+        .filter(|action| action.notify.is_none() && !action.on_error)
+        .map(|action| {
+            let method = &action.method;
+            let struct_ident = &action.struct_ident;
+            let fields = &action.fields;
+            let return_type = crate::eosio_action::return_type(&action.output);
+            quote! {
+                #[derive(Clone, #default #eosio::Read, #eosio::Write, #eosio::NumBytes)]
+                #[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+                pub struct #struct_ident {
+                    #(#fields)*
+                }
+
+                #[automatically_derived]
+                impl #eosio::ToAction for #struct_ident {
+                    const NAME: u64 = #eosio::n!(#method);
+                    #return_type
+                }
+            }
+        });
+    quote!(#(#structs)*)
+}
+
+/// `{Contract}Client`, with a method for every action
+fn client(self_ty: &Type, actions: &[ContractAction]) -> TokenStream2 {
+    let contract = match self_ty {
+        Type::Path(path) => match path.path.segments.iter().last() {
+            Some(segment) => &segment.ident,
+            None => return quote!(),
+        },
+        _ => return quote!(),
+    };
+    let ident = Ident::new(format!("{}Client", contract).as_str(), contract.span());
+    let client_struct = crate::eosio_action::client_struct(&ident);
+    let methods = actions
+        .iter()
+        .filter(|action| action.notify.is_none() && !action.on_error)
+        .map(|action| {
+            let args = action
+                .args
+                .iter()
+                .map(|arg| quote!(#arg))
+                .collect::<Vec<_>>();
+            crate::eosio_action::client_method(
+                &action.method,
+                &action.struct_ident,
+                &args,
+                &action.types,
+                false,
+                quote!(pub),
+            )
+        });
+    quote! {
+        #client_struct
+
+        impl #ident {
+            #(#methods)*
+        }
+    }
+}
+
+pub fn expand(_args: TokenStream, input: TokenStream) -> TokenStream {
+    let expanded = match parse_macro_input!(input as Item) {
+        Item::Struct(item) => contract_struct(item),
+        Item::Impl(item) => contract_impl(item),
+        _ => panic!("#[eosio_contract] can only be used on a struct or an impl block"),
+    };
+    TokenStream::from(expanded)
+}
+
+/// Adds `receiver` and `code` fields to the struct and implements `Contract`
+/// for it. Other fields are set with `#[init(expr)]`, where `expr` can use
+/// those two names, or with `Default::default()`.
+fn contract_struct(mut item: ItemStruct) -> TokenStream2 {
+    let eosio = crate::paths::eosio();
+    let mut fields = Vec::new();
+    let mut inits = Vec::new();
+    match item.fields {
+        Fields::Named(ref mut named) => {
+            for field in named.named.iter_mut() {
+                let mut init = None;
+                let mut attrs = Vec::new();
+                for attr in field.attrs.drain(..) {
+                    if attr.path.is_ident("init") {
+                        let parser = |input: ParseStream| {
+                            let content;
+                            parenthesized!(content in input);
+                            content.parse::<Expr>()
+                        };
+                        match parser.parse2(attr.tts) {
+                            Ok(expr) => init = Some(expr),
+                            Err(e) => panic!("invalid #[init] attribute: {}", e),
+                        }
+                    } else {
+                        attrs.push(attr);
+                    }
+                }
+                field.attrs = attrs;
+                let ident = field.ident.clone().unwrap();
+                let init = match init {
+                    Some(expr) => quote!(#expr),
+                    None => quote!(Default::default()),
+                };
+                inits.push(quote!(#ident: #init));
+                fields.push(field.clone());
+            }
+        }
+        Fields::Unit => (),
+        Fields::Unnamed(_) => panic!("#[eosio_contract] structs must have named fields"),
+    }
+
+    let attrs = &item.attrs;
+    let vis = &item.vis;
+    let ident = &item.ident;
+    let generics = &item.generics;
+    let (impl_generics, ty_generics, where_clause) = item.generics.split_for_impl();
+    quote! {
+        #(#attrs)*
+        #vis struct #ident #generics #where_clause {
+            /// The account whose code is executing
+            pub receiver: #eosio::AccountName,
+            /// The account the action was sent to, i.e. its first receiver
+            pub code: #eosio::AccountName,
+            #(#fields,)*
+        }
+
+        #[automatically_derived]
+        impl #impl_generics #eosio::Contract for #ident #ty_generics #where_clause {
+            fn new(receiver: #eosio::AccountName, code: #eosio::AccountName) -> Self {
+                #ident {
+                    #(#inits,)*
+                    receiver,
+                    code,
+                }
+            }
+        }
+    }
+}
+
+fn contract_impl(mut item: ItemImpl) -> TokenStream2 {
+    let actions = contract_actions(&mut item);
+    let structs = action_structs(&actions);
+    let client = client(&item.self_ty, &actions);
+    #[cfg(feature = "contract")]
+    let apply = apply(&item.self_ty, &actions);
+    #[cfg(not(feature = "contract"))]
+    let apply = quote!();
+    quote! {
+        #item
+
+        #structs
+
+        #client
+
+        #apply
+    }
+}
+
+/// The `apply` function, which dispatches to the methods in `actions`
+#[cfg(feature = "contract")]
+fn apply(self_ty: &Type, actions: &[ContractAction]) -> TokenStream2 {
+    let eosio = crate::paths::eosio();
+    let local_actions = actions
+        .iter()
+        .filter(|action| action.notify.is_none() && !action.on_error);
+    let notify_actions = actions.iter().filter(|action| action.notify.is_some());
+    let new_contract = |mutable: bool| {
+        let binding = if mutable {
+            quote!(mut contract)
+        } else {
+            quote!(contract)
+        };
+        quote! {
+            let #binding = <#self_ty as #eosio::Contract>::new(receiver.into(), code.into());
+        }
+    };
+    let local_contract = new_contract(local_actions.clone().any(|action| action.mutable));
+
+    let local_arms = local_actions.map(|action| {
+        let method = &action.method;
+        let struct_ident = &action.struct_ident;
+        let args = &action.args;
+        let call = match action.output {
+            ReturnType::Default => quote!(contract.#method(#(#args),*);),
+            ReturnType::Type(..) => quote! {
+                let value = contract.#method(#(#args),*);
+                #eosio::Assert::assert(#eosio::set_action_return_value(&value), "write");
+            },
+        };
+        quote! {
+            if action == #eosio::n!(#method) {
+                let (data, _) = #eosio::Assert::assert(
+                    #eosio::read_action_data::<#struct_ident>(),
+                    "read"
+                );
+                let #struct_ident { #(#args),* } = data;
+                #call
+                return;
+            }
+        }
+    });
+
+    let notify_arms = notify_actions.map(|action| {
+        let method = &action.method;
+        let condition = action.notify.as_ref().unwrap().condition();
+        let args = &action.args;
+        let types = &action.types;
+        let contract = new_contract(action.mutable);
+        quote! {
+            if #condition {
+                #[derive(#eosio::Read)]
+                struct Payload {
+                    #(#args: #types,)*
+                }
+                let (data, _) = #eosio::Assert::assert(
+                    #eosio::read_action_data::<Payload>(),
+                    "read"
+                );
+                let Payload { #(#args),* } = data;
+                #contract
+                contract.#method(#(#args),*);
+                return;
+            }
+        }
+    });
+
+    let on_error = actions.iter().find(|action| action.on_error).map(|action| {
+        let method = &action.method;
+        let contract = new_contract(action.mutable);
+        quote! {
+            let (error, _) = #eosio::Assert::assert(
+                #eosio::read_action_data::<#eosio::OnError>(),
+                "read"
+            );
+            #contract
+            contract.#method(error);
+            return;
+        }
+    });
+
+    quote! {
+        #[no_mangle]
+        pub extern "C" fn apply(receiver: u64, code: u64, action: u64) {
+            #eosio::runtime::init();
+            if action == #eosio::n!(onerror) {
+                #eosio::eosio_assert(
+                    code == #eosio::n!(eosio),
+                    "onerror action's are only valid from the \"eosio\" system account"
+                );
+                #on_error
+            }
+            if code == receiver {
+                #local_contract
+                #(#local_arms)*
+                #eosio::eosio_assert(false, "unknown action");
+            }
+            #(#notify_arms)*
+        }
+    }
+}
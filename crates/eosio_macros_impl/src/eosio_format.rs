@@ -0,0 +1,11 @@
+use crate::proc_macro::TokenStream;
+use quote::quote;
+
+pub fn expand(input: TokenStream) -> TokenStream {
+    let eosio = crate::paths::eosio();
+    let args = ::proc_macro2::TokenStream::from(input);
+    let expanded = quote! {
+        #eosio::format_to_string(format_args!(#args))
+    };
+    TokenStream::from(expanded)
+}
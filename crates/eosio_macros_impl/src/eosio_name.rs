@@ -38,6 +38,14 @@ pub fn expand(input: TokenStream) -> TokenStream {
         #[derive(#eosio::Read, #eosio::Write, #eosio::NumBytes, Debug, PartialEq, Eq, Clone, Copy, Default, Hash, PartialOrd, Ord)]
         pub struct #ident(u64);
 
+        #[automatically_derived]
+        impl #ident {
+            /// Wraps a raw name, e.g. from `n!`. Usable in constants.
+            pub const fn new(value: u64) -> Self {
+                #ident(value)
+            }
+        }
+
         #[automatically_derived]
         impl From<u64> for #ident {
             fn from(n: u64) -> Self {
@@ -164,6 +172,13 @@ pub fn expand(input: TokenStream) -> TokenStream {
                 serializer.serialize_str(self.to_string().as_str())
             }
         }
+
+        #[automatically_derived]
+        impl From<#ident> for #eosio::SecondaryKey {
+            fn from(key: #ident) -> Self {
+                #eosio::SecondaryKey::U64(key.0)
+            }
+        }
     };
     expanded.into()
 }
@@ -201,6 +216,14 @@ pub fn expand(input: TokenStream) -> TokenStream {
         #[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
         pub struct #ident(u64);
 
+        #[automatically_derived]
+        impl #ident {
+            /// Wraps a raw name, e.g. from `n!`. Usable in constants.
+            pub const fn new(value: u64) -> Self {
+                #ident(value)
+            }
+        }
+
         #[automatically_derived]
         impl From<u64> for #ident {
             fn from(n: u64) -> Self {
@@ -257,6 +280,13 @@ pub fn expand(input: TokenStream) -> TokenStream {
             }
         }
 
+        #[automatically_derived]
+        impl From<#ident> for #eosio::SecondaryKey {
+            fn from(key: #ident) -> Self {
+                #eosio::SecondaryKey::U64(key.0)
+            }
+        }
+
         #[automatically_derived]
         impl #eosio::SecondaryTableKey for #ident {
             fn end(
@@ -304,6 +334,14 @@ pub fn expand(input: TokenStream) -> TokenStream {
             ) -> (i32, u64) {
                 u64::from(*self).upper_bound(code, scope, table)
             }
+            fn begin(
+                &self,
+                code: #eosio::AccountName,
+                scope: #eosio::ScopeName,
+                table: #eosio::SecondaryTableName,
+            ) -> (i32, u64) {
+                u64::from(*self).begin(code, scope, table)
+            }
             fn find_primary(
                 &self,
                 code: #eosio::AccountName,
@@ -0,0 +1,18 @@
+use crate::proc_macro::TokenStream;
+use quote::quote;
+
+pub fn expand(input: TokenStream) -> TokenStream {
+    let eosio = crate::paths::eosio();
+    let args = ::proc_macro2::TokenStream::from(input);
+    let expanded = if args.is_empty() {
+        quote! {
+            #eosio::Print::print(&"\n");
+        }
+    } else {
+        quote! {
+            #eosio::print_args(format_args!(#args));
+            #eosio::Print::print(&"\n");
+        }
+    };
+    TokenStream::from(expanded)
+}
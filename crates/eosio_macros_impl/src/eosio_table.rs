@@ -1,16 +1,74 @@
 use crate::proc_macro::TokenStream;
 use proc_macro2::{Ident, Span};
 use quote::quote;
-use syn::{parse_macro_input, DeriveInput, LitStr};
+use syn::parse::{Error, Parse, ParseStream, Result};
+use syn::{parse_macro_input, DeriveInput, LitInt, LitStr, Token};
+
+/// The arguments to `#[eosio_table(name)]`, `#[eosio_table(name, singleton)]`
+/// or `#[eosio_table(name, version = 2)]`
+struct TableArgs {
+    name: Ident,
+    singleton: bool,
+    version: Option<LitInt>,
+}
+
+impl Parse for TableArgs {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let name: Ident = input.parse()?;
+        let mut singleton = false;
+        let mut version = None;
+        while input.parse::<Option<Token![,]>>()?.is_some() {
+            let arg: Ident = input.parse()?;
+            if arg == "singleton" {
+                singleton = true;
+            } else if arg == "version" {
+                input.parse::<Token![=]>()?;
+                version = Some(input.parse()?);
+            } else {
+                return Err(Error::new(arg.span(), "expected `singleton` or `version`"));
+            }
+        }
+        if singleton && version.is_some() {
+            return Err(Error::new(
+                Span::call_site(),
+                "singletons can't be versioned",
+            ));
+        }
+        Ok(TableArgs {
+            name,
+            singleton,
+            version,
+        })
+    }
+}
 
 pub fn expand(args: TokenStream, input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
     let eosio = crate::paths::eosio();
-    let name = parse_macro_input!(args as Ident);
+    let args = parse_macro_input!(args as TableArgs);
+    let name = args.name;
+    if args.singleton {
+        let ident = &input.ident;
+        let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+        let expanded = quote! {
+            #[derive(Debug, #eosio::Read, #eosio::Write, #eosio::NumBytes, Clone, PartialEq, PartialOrd)]
+            #input
+
+            #[automatically_derived]
+            impl #impl_generics #eosio::SingletonRow for #ident #ty_generics #where_clause {
+                const TABLE_NAME: #eosio::TableName = #eosio::TableName::new(#eosio::n!(#name));
+            }
+        };
+        return TokenStream::from(expanded);
+    }
     let name = LitStr::new(format!("{}", quote!(#name)).as_str(), Span::call_site());
+    let version = args
+        .version
+        .map(|version| quote!(#[table_version = #version]));
     let expanded = quote! {
         #[derive(Debug, #eosio::TableRow, #eosio::Read, #eosio::Write, #eosio::NumBytes, Clone, PartialEq, PartialOrd)]
         #[table_name = #name]
+        #version
         #input
     };
     TokenStream::from(expanded)
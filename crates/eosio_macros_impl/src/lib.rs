@@ -4,6 +4,7 @@
 
 extern crate proc_macro;
 
+mod derive_contract_error;
 mod derive_num_bytes;
 mod derive_print;
 mod derive_read;
@@ -11,8 +12,11 @@ mod derive_table_row;
 mod derive_write;
 mod eosio_abi;
 mod eosio_action;
+mod eosio_contract;
+mod eosio_format;
 mod eosio_name;
 mod eosio_print;
+mod eosio_println;
 mod eosio_table;
 mod n;
 mod s;
@@ -42,6 +46,11 @@ pub fn eosio_action(args: TokenStream, input: TokenStream) -> TokenStream {
     crate::eosio_action::expand(args, input)
 }
 
+#[proc_macro_attribute]
+pub fn eosio_contract(args: TokenStream, input: TokenStream) -> TokenStream {
+    crate::eosio_contract::expand(args, input)
+}
+
 #[proc_macro]
 pub fn eosio_name(input: TokenStream) -> TokenStream {
     crate::eosio_name::expand(input)
@@ -52,6 +61,16 @@ pub fn eosio_print(input: TokenStream) -> TokenStream {
     crate::eosio_print::expand(input)
 }
 
+#[proc_macro_hack]
+pub fn eosio_format(input: TokenStream) -> TokenStream {
+    crate::eosio_format::expand(input)
+}
+
+#[proc_macro_hack]
+pub fn eosio_println(input: TokenStream) -> TokenStream {
+    crate::eosio_println::expand(input)
+}
+
 #[proc_macro_attribute]
 pub fn eosio_table(args: TokenStream, input: TokenStream) -> TokenStream {
     crate::eosio_table::expand(args, input)
@@ -77,11 +96,24 @@ pub fn derive_read(input: TokenStream) -> TokenStream {
     crate::derive_read::expand(input)
 }
 
-#[proc_macro_derive(TableRow, attributes(table_name, primary, secondary))]
+#[proc_macro_derive(
+    TableRow,
+    attributes(table_name, table_version, scope, primary, secondary)
+)]
 pub fn derive_table_row(input: TokenStream) -> TokenStream {
     crate::derive_table_row::expand(input)
 }
 
+#[proc_macro_derive(Print)]
+pub fn derive_print(input: TokenStream) -> TokenStream {
+    crate::derive_print::expand(input)
+}
+
+#[proc_macro_derive(ContractError, attributes(error))]
+pub fn derive_contract_error(input: TokenStream) -> TokenStream {
+    crate::derive_contract_error::expand(input)
+}
+
 #[proc_macro_derive(NumBytes)]
 pub fn derive_num_bytes(input: TokenStream) -> TokenStream {
     crate::derive_num_bytes::expand(input)
@@ -24,6 +24,8 @@ pub struct Abi {
     ricardian_clauses: Vec<RicardianClause>,
     error_messages: Vec<ErrorMessage>,
     abi_extensions: Vec<AbiExtension>,
+    #[serde(default)]
+    action_results: Vec<ActionResult>,
     // TODO variants: Vec<Variant>,
 }
 
@@ -85,6 +87,12 @@ pub struct AbiExtension {
     data: String,
 }
 
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ActionResult {
+    name: String,
+    result_type: String,
+}
+
 // #[derive(Serialize, Deserialize, Debug)]
 // pub struct Variant {}
 
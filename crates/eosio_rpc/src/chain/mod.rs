@@ -8,6 +8,7 @@ pub mod get_currency_balance;
 pub mod get_currency_stats;
 pub mod get_info;
 pub mod get_table_rows;
+pub mod push_transaction;
 
 pub use self::get_abi::{get_abi, GetAbi};
 pub use self::get_account::{get_account, GetAccount};
@@ -16,3 +17,4 @@ pub use self::get_currency_balance::{get_currency_balance, GetCurrencyBalance};
 pub use self::get_currency_stats::{get_currency_stats, GetCurrencyStats};
 pub use self::get_info::{get_info, GetInfo};
 pub use self::get_table_rows::{get_table_rows, GetTableRows};
+pub use self::push_transaction::{push_transaction, PushTransaction};
@@ -1 +1,85 @@
+use eosio::{AccountName, ActionName, Authorization, Read};
+use serde_derive::{Deserialize, Serialize};
+
 const PATH: &str = "/v1/chain/push_transaction";
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PackedTransaction {
+    pub signatures: Vec<String>,
+    pub compression: String,
+    pub packed_context_free_data: String,
+    pub packed_trx: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct PushTransaction {
+    pub transaction_id: String,
+    pub processed: TransactionTrace,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct TransactionTrace {
+    pub id: String,
+    pub block_num: u32,
+    pub block_time: String,
+    pub elapsed: i64,
+    pub net_usage: u64,
+    pub scheduled: bool,
+    pub action_traces: Vec<ActionTrace>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ActionTrace {
+    pub receiver: AccountName,
+    pub act: TraceAction,
+    pub elapsed: i64,
+    pub console: String,
+    pub trx_id: String,
+    pub block_num: u32,
+    pub block_time: String,
+    /// The packed value returned by the action, as hex
+    #[serde(default)]
+    pub return_value_hex_data: String,
+    /// The returned value decoded by `nodeos`, if the ABI has an action result
+    #[serde(default)]
+    pub return_value_data: Option<::serde_json::Value>,
+    #[serde(default)]
+    pub inline_traces: Vec<ActionTrace>,
+}
+
+impl ActionTrace {
+    /// Decodes the value returned by the action. Returns `None` if the action
+    /// didn't return a value or it couldn't be read as `T`.
+    pub fn return_value<T>(&self) -> Option<T>
+    where
+        T: Read,
+    {
+        let hex = self.return_value_hex_data.as_bytes();
+        if hex.is_empty() || hex.len() % 2 != 0 {
+            return None;
+        }
+        let mut bytes = Vec::with_capacity(hex.len() / 2);
+        for pair in hex.chunks(2) {
+            let pair = ::std::str::from_utf8(pair).ok()?;
+            bytes.push(u8::from_str_radix(pair, 16).ok()?);
+        }
+        T::read(&bytes, 0).ok().map(|(value, _)| value)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct TraceAction {
+    pub account: AccountName,
+    pub name: ActionName,
+    pub authorization: Vec<Authorization>,
+    pub data: ::serde_json::Value,
+    #[serde(default)]
+    pub hex_data: Option<String>,
+}
+
+pub fn push_transaction(
+    node: &str,
+    transaction: PackedTransaction,
+) -> impl ::futures::Future<Item = PushTransaction, Error = crate::Error> {
+    crate::http::post(node, PATH, transaction)
+}
@@ -2,6 +2,7 @@
 
 pub type uint128_t = [u64; 2];
 pub type int128_t = [u64; 2];
+pub type float128_t = [u64; 2];
 pub type capi_name = u64;
 /// @brief EOSIO Public Key
 /// @details EOSIO Public Key. It is 34 bytes.
@@ -240,6 +241,14 @@ extern "C" {
     ///  @return the account which specifies the current receiver of the action
     pub fn current_receiver() -> capi_name;
 }
+extern "C" {
+    ///  Set the action return value which will be included in the action_receipt
+    ///  @brief Set the action return value
+    ///  @param return_value - serialized return value
+    ///  @param size - size of serialized return value in bytes
+    ///  @pre `return_value` is a valid pointer to an array at least `size` bytes long
+    pub fn set_action_return_value(return_value: *mut crate::ctypes::c_void, size: usize);
+}
 extern "C" {
     ///  Gets the set of active producers.
     ///  @brief Gets the set of active producers.
@@ -1296,7 +1305,7 @@ extern "C" {
         table: capi_name,
         payer: capi_name,
         id: u64,
-        secondary: *const f64,
+        secondary: *const float128_t,
     ) -> i32;
 }
 extern "C" {
@@ -1308,7 +1317,7 @@ extern "C" {
     ///  @param secondary - Pointer to the **new** secondary key that will replace the existing one of the association
     ///  @pre `iterator` points to an existing table row in the table
     ///  @post the secondary key of the table row pointed to by `iterator` is replaced by `*secondary`
-    pub fn db_idx_long_double_update(iterator: i32, payer: capi_name, secondary: *const f64);
+    pub fn db_idx_long_double_update(iterator: i32, payer: capi_name, secondary: *const float128_t);
 }
 extern "C" {
     ///  Remove a table row from a secondary quadruple-precision floating-point index table
@@ -1356,7 +1365,7 @@ extern "C" {
         code: capi_name,
         scope: u64,
         table: capi_name,
-        secondary: *mut f64,
+        secondary: *mut float128_t,
         primary: u64,
     ) -> i32;
 }
@@ -1375,7 +1384,7 @@ extern "C" {
         code: capi_name,
         scope: u64,
         table: capi_name,
-        secondary: *const f64,
+        secondary: *const float128_t,
         primary: *mut u64,
     ) -> i32;
 }
@@ -1396,7 +1405,7 @@ extern "C" {
         code: capi_name,
         scope: u64,
         table: capi_name,
-        secondary: *mut f64,
+        secondary: *mut float128_t,
         primary: *mut u64,
     ) -> i32;
 }
@@ -1417,7 +1426,7 @@ extern "C" {
         code: capi_name,
         scope: u64,
         table: capi_name,
-        secondary: *mut f64,
+        secondary: *mut float128_t,
         primary: *mut u64,
     ) -> i32;
 }
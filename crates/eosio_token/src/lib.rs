@@ -26,11 +26,11 @@ fn create(issuer: AccountName, max_supply: Asset) {
 }
 
 #[eosio_action]
+#[check(memo.len() <= 256, "memo has more than 256 bytes")]
 fn issue(to: AccountName, quantity: Asset, memo: String) {
     let receiver = AccountName::receiver();
     let symbol = quantity.symbol;
 
-    eosio_assert(memo.len() <= 256, "memo has more than 256 bytes");
     let table = CurrencyStats::table(receiver, symbol.name());
     let cursor = table
         .find(symbol.name())
@@ -55,26 +55,22 @@ fn issue(to: AccountName, quantity: Asset, memo: String) {
     add_balance(st.issuer, quantity, st.issuer);
 
     if to != st.issuer {
-        let action = TransferAction {
-            from: st.issuer,
-            to,
-            quantity,
-            memo,
-        };
-        action
-            .send_inline(vec![Authorization {
-                actor: st.issuer,
-                permission: n!(active).into(),
-            }])
-            .assert("failed to send inline action");
+        TokenClient::new(receiver)
+            .transfer(
+                Authorization::active(st.issuer),
+                st.issuer,
+                to,
+                quantity,
+                memo,
+            )
+            .send();
     }
 }
 
-#[eosio_action]
+#[eosio_action(auth = "ram_payer")]
 fn open(owner: AccountName, symbol: Symbol, ram_payer: AccountName) {
-    require_auth(ram_payer);
     let receiver = AccountName::receiver();
-    let accounts_table = Account::table(receiver, symbol.name());
+    let accounts_table = Account::table(receiver, owner);
     let cursor = accounts_table.find(symbol.name());
     if cursor.is_none() {
         let account = Account {
@@ -84,11 +80,10 @@ fn open(owner: AccountName, symbol: Symbol, ram_payer: AccountName) {
     }
 }
 
-#[eosio_action]
+#[eosio_action(auth = "owner")]
 fn close(owner: AccountName, symbol: Symbol) {
-    require_auth(owner);
     let receiver = AccountName::receiver();
-    let accounts_table = Account::table(receiver, symbol.name());
+    let accounts_table = Account::table(receiver, owner);
     let cursor = accounts_table
         .find(symbol.name())
         .assert("Balance row already deleted or never existed. Action won't have any effect.");
@@ -103,9 +98,8 @@ fn close(owner: AccountName, symbol: Symbol) {
 }
 
 #[eosio_action]
+#[check(memo.len() <= 256, "memo has more than 256 bytes")]
 fn retire(quantity: Asset, memo: String) {
-    eosio_assert(memo.len() <= 256, "memo has more than 256 bytes");
-
     let receiver = AccountName::receiver();
     let symbol = quantity.symbol;
     let stats_table = CurrencyStats::table(receiver, symbol.name());
@@ -125,10 +119,10 @@ fn retire(quantity: Asset, memo: String) {
     cursor.modify(None, &st).assert("write");
 }
 
-#[eosio_action]
+#[eosio_action(auth = "from", notify = ["from", "to"])]
+#[check(from != to, "cannot transfer to self")]
+#[check(memo.len() <= 256, "memo has more than 256 bytes")]
 fn transfer(from: AccountName, to: AccountName, quantity: Asset, memo: String) {
-    eosio_assert(from != to, "cannot transfer to self");
-    require_auth(from);
     to.is_account().assert("to account does not exist");
 
     let receiver = AccountName::receiver();
@@ -139,15 +133,11 @@ fn transfer(from: AccountName, to: AccountName, quantity: Asset, memo: String) {
         .assert("token with symbol does not exist");
     let st = cursor.get().assert("read");
 
-    require_recipient(from);
-    require_recipient(to);
-
     eosio_assert(quantity.amount > 0, "must transfer positive quantity");
     eosio_assert(
         quantity.symbol == st.supply.symbol,
         "symbol precision mismatch",
     );
-    eosio_assert(memo.len() <= 256, "memo has more than 256 bytes");
 
     let payer = if to.has_auth() { to } else { from };
 
@@ -155,7 +145,15 @@ fn transfer(from: AccountName, to: AccountName, quantity: Asset, memo: String) {
     add_balance(to, quantity, payer);
 }
 
-eosio_abi!(create, issue, transfer, open, close, retire);
+eosio_abi!(
+    create,
+    issue,
+    transfer,
+    open,
+    close,
+    retire,
+    client = TokenClient
+);
 
 #[cfg(feature = "contract")]
 fn sub_balance(owner: AccountName, value: Asset) {
@@ -197,7 +195,8 @@ pub struct Account {
 
 #[cfg(feature = "contract")]
 impl TableRow for Account {
-    const TABLE_NAME: u64 = n!(accounts);
+    const TABLE_NAME: TableName = TableName::new(n!(accounts));
+    type Scope = AccountName;
 
     fn primary_key(&self) -> u64 {
         self.balance.symbol.name().into()
@@ -213,7 +212,8 @@ pub struct CurrencyStats {
 
 #[cfg(feature = "contract")]
 impl TableRow for CurrencyStats {
-    const TABLE_NAME: u64 = n!(stat);
+    const TABLE_NAME: TableName = TableName::new(n!(stat));
+    type Scope = SymbolName;
 
     fn primary_key(&self) -> u64 {
         self.supply.symbol.name().into()